@@ -1,5 +1,5 @@
 use rain::Graph;
-use rand::distributions::{Distribution, Range};
+use rand::distributions::{Distribution, Uniform};
 use std::{thread, time::Duration};
 
 fn main() {
@@ -9,7 +9,7 @@ fn main() {
     let l3 = "Gamma";
 
     fn sleep() {
-        let between = Range::new(100, 800);
+        let between = Uniform::new(100, 800);
         let mut rng = rand::thread_rng();
         thread::sleep(Duration::from_millis(between.sample(&mut rng)));
     }
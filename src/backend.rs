@@ -0,0 +1,124 @@
+//! Terminal backend abstraction, which uses `termion` by default and
+//! `crossterm` if the default features are disabled and the corresponding
+//! feature is enabled
+use std::io;
+
+#[cfg(not(any(feature = "termion", feature = "crossterm")))]
+compile_error!("either the `termion` or `crossterm` feature has to be enabled");
+
+#[cfg(all(feature = "termion", feature = "crossterm"))]
+compile_error!(
+    "the `termion` and `crossterm` features are mutually exclusive, disable \
+     the default features to use `crossterm`"
+);
+
+/// Returns the terminal size as `(columns, rows)`
+pub fn terminal_size() -> io::Result<(u16, u16)> {
+    #[cfg(feature = "crossterm")]
+    return crossterm::terminal::size();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::terminal_size()
+}
+
+/// Sets the foreground to the provided RGB color
+pub fn fg_rgb(r: u8, g: u8, b: u8) -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetForegroundColor(
+        crossterm::style::Color::Rgb { r, g, b },
+    )
+    .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::color::Fg(termion::color::Rgb(r, g, b)).to_string()
+}
+
+/// Sets the foreground to the provided color of the 256 color palette
+pub fn fg_ansi(index: u8) -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetForegroundColor(
+        crossterm::style::Color::AnsiValue(index),
+    )
+    .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::color::Fg(termion::color::AnsiValue(index)).to_string()
+}
+
+/// Sets the foreground to the provided basic ANSI color between 0 (black)
+/// and 7 (white)
+pub fn fg_basic(index: u8) -> String {
+    #[cfg(feature = "crossterm")]
+    use crossterm::style::Color;
+    #[cfg(not(feature = "crossterm"))]
+    use termion::color::{self, Fg};
+
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetForegroundColor(match index {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        _ => Color::Grey,
+    })
+    .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    match index {
+        0 => Fg(color::Black).to_string(),
+        1 => Fg(color::Red).to_string(),
+        2 => Fg(color::Green).to_string(),
+        3 => Fg(color::Yellow).to_string(),
+        4 => Fg(color::Blue).to_string(),
+        5 => Fg(color::Magenta).to_string(),
+        6 => Fg(color::Cyan).to_string(),
+        _ => Fg(color::White).to_string(),
+    }
+}
+
+/// Sets the foreground to the color of the fill characters
+pub fn fg_fill() -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetForegroundColor(
+        crossterm::style::Color::DarkGrey,
+    )
+    .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::color::Fg(termion::color::LightBlack).to_string()
+}
+
+/// Resets the foreground color
+pub fn fg_reset() -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetForegroundColor(
+        crossterm::style::Color::Reset,
+    )
+    .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::color::Fg(termion::color::Reset).to_string()
+}
+
+/// Enables bold text
+pub fn bold() -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetAttribute(crossterm::style::Attribute::Bold)
+        .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::style::Bold.to_string()
+}
+
+/// Resets all colors and text attributes
+pub fn reset() -> String {
+    #[cfg(feature = "crossterm")]
+    return crossterm::style::SetAttribute(crossterm::style::Attribute::Reset)
+        .to_string();
+
+    #[cfg(not(feature = "crossterm"))]
+    termion::style::Reset.to_string()
+}
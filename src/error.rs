@@ -0,0 +1,170 @@
+//! Basic error handling mechanisms
+use std::{error::Error, fmt, io};
+
+/// The result type for the graph
+pub type RainResult<T> = Result<T, RainError>;
+
+/// Concrete errors
+///
+/// The error is `Send + Sync + 'static`, which means it can be converted into
+/// a `Box<dyn Error + Send + Sync>` by the `?` operator.
+pub struct RainError {
+    /// A further description for the error
+    pub description: String,
+
+    /// The cause for this error
+    pub source: Option<Box<dyn Error + Send + Sync>>,
+
+    /// The related error type
+    pub code: ErrorType,
+}
+
+/// Representation for the error types
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ErrorType {
+    /// The line does not exist within the graph
+    LineDoesNotExist {
+        /// The name of the line
+        name: String,
+    },
+
+    /// The line does already exist within the graph
+    LineAlreadyExists {
+        /// The name of the line
+        name: String,
+    },
+
+    /// The line has already been removed since the last print
+    DuplicateRemoval {
+        /// The name of the line
+        name: String,
+    },
+
+    /// Adding the line would exceed the maximum amount of columns
+    MaxColumnsExceeded {
+        /// The maximum allowed amount of columns
+        limit: usize,
+
+        /// The amount of columns which would have been used
+        attempted: usize,
+    },
+
+    /// The prefix would not leave any space for the graph
+    PrefixTooLong {
+        /// The requested prefix length
+        length: usize,
+
+        /// The maximum possible prefix length
+        max_length: usize,
+    },
+
+    /// The columns do not fit into the terminal
+    ColumnsOverflow {
+        /// The amount of columns which fit into the terminal
+        visible: usize,
+
+        /// The amount of all columns
+        total: usize,
+    },
+
+    /// The terminal is not wide enough to print a single column
+    TerminalTooNarrow {
+        /// The current terminal width
+        width: u16,
+
+        /// The minimum required terminal width
+        required: usize,
+    },
+
+    /// The character does not have the expected width
+    InvalidCharacterWidth {
+        /// The provided character
+        character: char,
+
+        /// The width of the character in terminal columns
+        width: usize,
+    },
+
+    /// The marker does not have the expected width
+    InvalidMarkerWidth {
+        /// The provided marker
+        marker: String,
+
+        /// The width of the marker in terminal columns
+        width: usize,
+    },
+
+    /// The format is not supported
+    InvalidFormat {
+        /// The provided format
+        format: String,
+    },
+
+    /// The group does not exist within the graph
+    GroupDoesNotExist {
+        /// The name of the group
+        name: String,
+    },
+
+    /// The added value exceeded the bounds and has been clamped
+    ValueClamped {
+        /// The name of the line
+        name: String,
+    },
+
+    /// The lock of a shared graph is poisoned
+    LockPoisoned,
+
+    /// Any other error, like I/O or formatting failures
+    Other,
+}
+
+/// Return early with a new error of the provided type and description
+macro_rules! bail {
+    ($code:expr, $($fmt:tt)*) => {
+        return Err(crate::error::RainError {
+            description: format!($($fmt)*),
+            source: None,
+            code: $code,
+        })
+    };
+}
+
+macro_rules! from_error {
+    ($($p:ty,)*) => (
+        $(impl From<$p> for RainError {
+            fn from(err: $p) -> RainError {
+                RainError {
+                    description: err.to_string(),
+                    code: ErrorType::Other,
+                    source: Some(Box::new(err)),
+                }
+            }
+        })*
+    )
+}
+
+from_error! {
+    fmt::Error,
+    io::Error,
+}
+
+impl fmt::Display for RainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.description)
+    }
+}
+
+impl fmt::Debug for RainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}: {}", self.code, self.description)
+    }
+}
+
+impl Error for RainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn Error + 'static))
+    }
+}
@@ -32,18 +32,88 @@
 //! assert!(graph.remove(l1).is_ok());
 //! graph.print();
 //! ```
+//!
+//! # Terminal backends
+//! The terminal is accessed via [termion](https://crates.io/crates/termion) by
+//! default, which does not support Windows. The
+//! [crossterm](https://crates.io/crates/crossterm) backend works on Windows,
+//! too, and is selected at compile time by replacing the default features:
+//!
+//! ```toml
+//! [dependencies]
+//! rain = { version = "2", default-features = false, features = ["crossterm"] }
+//! ```
+//!
+//! Both backends are mutually exclusive and there is no runtime switch like a
+//! `Graph::with_crossterm_backend()`, the public API of the `Graph` stays the
+//! same for both of them. Add the `mowl` feature again if
+//! `Graph::set_log_level` is needed.
 #![deny(missing_docs)]
 
-use failure::{bail, Fallible};
-use log::{debug, info, warn, LevelFilter};
-use std::{cmp::max, convert, fmt, iter, u8};
-use termion::color::{self, Fg, LightBlack, Reset};
+#[macro_use]
+mod error;
+mod backend;
+mod macros;
+mod shared;
+mod snapshot;
+
+#[doc(hidden)]
+pub use crate::macros::unique_names;
+pub use crate::{
+    error::{ErrorType, RainError, RainResult},
+    shared::SharedGraph,
+    snapshot::{Replay, Snapshot, SnapshotDiff},
+};
+
+use chrono::Local;
+#[cfg(feature = "async")]
+use futures::{pin_mut, Stream, StreamExt};
+#[cfg(feature = "mowl")]
+use log::LevelFilter;
+#[cfg(not(feature = "tracing"))]
+use log::{debug, warn};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::max,
+    convert::{self, TryFrom},
+    fmt, fs,
+    io::{self, IsTerminal, Write},
+    mem, ops,
+    path::Path,
+    str,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "tracing")]
+use tracing::{debug, warn};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// A callback which is invoked after every printed row
+type PrintHook<V> = Box<dyn Fn(&Graph<V>) + Send>;
 
 /// The graph drawing structure
 pub struct Graph<V> {
+    config: GraphConfig,
     lines_to_be_removed: Vec<String>,
     columns: Vec<Column<V>>,
-    prefix_len: usize,
+    axis: Option<V>,
+    print_count: usize,
+    output: OutputTarget,
+    last_print: Option<Instant>,
+    last_add: Option<Instant>,
+    cached_width: Option<u16>,
+    metrics: Option<GraphMetrics>,
+    last_render_time: Option<Duration>,
+    error_handler: Option<Box<dyn Fn(RainError) + Send>>,
+    print_hook: Option<PrintHook<V>>,
+    buffer: Option<Vec<String>>,
+    accumulate: Option<fn(V, V) -> V>,
+    clamping: Option<(V, V)>,
+    transform: Option<Box<dyn Fn(V) -> V + Send>>,
+    viewport_offset: usize,
+    peak_line_count: usize,
 }
 
 impl<V> Graph<V>
@@ -73,358 +143,4388 @@ where
     /// let _: Graph<u8> = Graph::with_prefix_length(25);
     /// ```
     pub fn with_prefix_length(length: usize) -> Self {
-        Graph {
-            lines_to_be_removed: vec![],
-            columns: vec![],
+        Self::new_with_config(GraphConfig {
             prefix_len: length + 3,
-        }
+            ..GraphConfig::default()
+        })
     }
 
-    /// Set the global log level for reporting
-    pub fn set_log_level(self, level: LevelFilter) -> Self {
-        // Setup the logger if not already set
-        if mowl::init_with_level(level).is_err() {
-            warn!("Logger already set.");
-        } else {
-            info!("Log level set to: {:?}", level);
+    /// Create a new `Graph` for drawing from an existing configuration, for
+    /// example the one of another graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let first: Graph<u8> = Graph::with_prefix_length(25).with_bold_latest();
+    /// let second: Graph<u8> = Graph::new_with_config(first.config().clone());
+    /// assert!(second.config().bold_latest);
+    /// ```
+    pub fn new_with_config(config: GraphConfig) -> Self {
+        Graph {
+            config,
+            lines_to_be_removed: vec![],
+            columns: vec![],
+            axis: None,
+            print_count: 0,
+            output: OutputTarget::Stdout,
+            last_print: None,
+            last_add: None,
+            cached_width: None,
+            metrics: None,
+            last_render_time: None,
+            error_handler: None,
+            print_hook: None,
+            buffer: None,
+            accumulate: None,
+            clamping: None,
+            transform: None,
+            viewport_offset: 0,
+            peak_line_count: 0,
         }
-        self
     }
 
-    /// Add a data value to the graph by some identifier which can be displayed
-    /// somehow.
+    /// Create a new `Graph` from a CSV file as written by `export_csv` or
+    /// `export_csv_with_timestamps`. The first row contains the tick column,
+    /// the optional time column and the unique names of the lines, every
+    /// further row contains the values of a single tick.
     ///
     /// # Example
     /// ```
     /// use rain::Graph;
     ///
     /// let mut graph = Graph::new();
-    /// let line = graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
     ///
-    /// assert_eq!(line, "Line 1");
+    /// let path = std::env::temp_dir().join("rain_doc_load.csv");
+    /// let mut file = std::fs::File::create(&path).unwrap();
+    /// graph.export_csv(&mut file).unwrap();
+    ///
+    /// let loaded: Graph<u32> = Graph::with_load_from_csv(&path).unwrap();
+    /// assert_eq!(loaded.line_values("Line 1"), Some(&[1, 2][..]));
     /// ```
-    pub fn add<T>(&mut self, identifier: T, value: V) -> Fallible<T>
+    pub fn with_load_from_csv<P>(path: P) -> RainResult<Self>
     where
-        T: fmt::Display,
+        P: AsRef<Path>,
+        V: str::FromStr,
+        V::Err: std::error::Error + Send + Sync + 'static,
     {
-        // Get a line name string from the identifier
-        let line_name = format!("{}", identifier);
-        debug!("Adding value {:?} to line '{}'", value, line_name);
+        let content = fs::read_to_string(path)?;
+        let mut rows = content.lines();
+        let header = match rows.next() {
+            Some(header) => split_csv(header),
+            None => bail!(ErrorType::Other, "CSV header row is missing"),
+        };
 
-        // Just add the value if the line already exist
-        let add_new_line = {
-            if let Some(line) = self.line_already_existing(&line_name) {
-                debug!("Line already exist, just adding the value");
-                line.add_value(value.clone());
-                false
-            } else {
-                true
+        // Skip the tick and, if exported with timestamps, the time column
+        let skipped = match (header.first(), header.get(1)) {
+            (Some(tick), Some(time))
+                if tick == "tick" && time == "elapsed_ms" =>
+            {
+                2
+            }
+            (Some(tick), _) if tick == "tick" => 1,
+            _ => {
+                bail!(ErrorType::Other, "CSV header does not start with 'tick'")
             }
         };
 
-        // Add a new line and set the column as used
-        if add_new_line {
-            debug!("Adding new line");
-            let column = self.get_next_free_column();
-            let mut line = Line::new(&line_name);
-            line.add_value(value);
-            *column = Column::Used(line);
+        // Add the lines in the order of the header
+        let mut graph = Self::new();
+        let names = header.iter().skip(skipped).cloned().collect::<Vec<_>>();
+        for (index, name) in names.iter().enumerate() {
+            if name.is_empty() {
+                bail!(ErrorType::Other, "CSV header contains an empty name");
+            }
+            if names[..index].contains(name) {
+                bail!(
+                    ErrorType::Other,
+                    "CSV header contains the name '{}' multiple times",
+                    name
+                );
+            }
+            *graph.get_next_free_column() = Column::Used(Line::new(name));
         }
 
-        Ok(identifier)
+        for (index, row) in rows.enumerate() {
+            let fields = split_csv(row);
+            if fields.len() != header.len() {
+                bail!(
+                    ErrorType::Other,
+                    "CSV row {} contains {} instead of {} fields",
+                    index + 1,
+                    fields.len(),
+                    header.len()
+                );
+            }
+            for (name, field) in names.iter().zip(fields.iter().skip(skipped)) {
+                if field.is_empty() {
+                    continue;
+                }
+                let value = field.parse::<V>().map_err(|e| RainError {
+                    description: format!(
+                        "Unable to parse value '{}' of line '{}'",
+                        field, name
+                    ),
+                    source: Some(Box::new(e)),
+                    code: ErrorType::Other,
+                })?;
+                if let Some(line) = graph.line_already_existing(name) {
+                    line.add_value(value);
+                }
+            }
+        }
+        Ok(graph)
     }
 
-    /// Remove a line from the graph
+    /// Create a new `Graph` for drawing with a custom length of the identifier
+    /// (prefix), which fails if the prefix would not leave any space for the
+    /// graph within the current terminal. A terminal width of 80 columns is
+    /// assumed if the width can not be determined.
     ///
     /// # Example
     /// ```
     /// use rain::Graph;
     ///
-    /// let mut graph = Graph::new();
-    /// let line = graph.add("Line 1", 0).unwrap();
-    ///
-    /// let removed_line = graph.remove(line).unwrap();
-    /// assert_eq!(removed_line, "Line 1");
+    /// assert!(Graph::<u8>::try_with_prefix_length(25).is_ok());
+    /// assert!(Graph::<u8>::try_with_prefix_length(1000).is_err());
     /// ```
-    pub fn remove<T>(&mut self, identifier: T) -> Fallible<T>
-    where
-        T: fmt::Display,
-    {
-        // Check if the line exists
-        let line_name = format!("{}", identifier);
-        if self.line_already_existing(&line_name).is_none() {
-            bail!("Line does not exist and can not be removed");
-        }
+    pub fn try_with_prefix_length(length: usize) -> RainResult<Self> {
+        let width = backend::terminal_size().map(|(w, _)| w).unwrap_or(80);
 
-        // Just push the line into a temporarily vector
-        self.lines_to_be_removed.push(line_name);
-
-        Ok(identifier)
+        // Keep space for the markers, a single column and the overflow sign
+        let max_length = usize::from(width).saturating_sub(3 + 2 + 1);
+        if length > max_length {
+            bail!(
+                ErrorType::PrefixTooLong { length, max_length },
+                "Prefix length {} exceeds the maximum of {}",
+                length,
+                max_length
+            );
+        }
+        Ok(Self::with_prefix_length(length))
     }
 
-    /// Prints the graph
+    /// Returns the configuration of the graph
     ///
     /// # Example
     /// ```
     /// use rain::Graph;
     ///
-    /// let mut graph = Graph::new();
+    /// let graph: Graph<u8> = Graph::new();
+    /// assert_eq!(graph.config().prefix_len, 11);
+    /// ```
+    pub fn config(&self) -> &GraphConfig {
+        &self.config
+    }
+
+    /// Returns the configuration of the graph for modification
     ///
-    /// assert!(graph.add("Line 1", 0).is_ok());
-    /// assert!(graph.add("Line 2", 0).is_ok());
+    /// # Example
+    /// ```
+    /// use rain::Graph;
     ///
-    /// graph.print();
+    /// let mut graph: Graph<u8> = Graph::new();
+    /// graph.config_mut().soft_scale = 0;
+    /// assert_eq!(graph.config().soft_scale, 0);
     /// ```
-    pub fn print(&mut self) -> Fallible<()> {
-        /// Prints the fillchar to the terminal
-        fn fillchar() -> String {
-            format!("{}┈{}", Fg(LightBlack), Fg(Reset))
-        }
-
-        // Do the actual printing per column
-        let start_ch = "┬";
-        let line_chr = "│";
-        let nodata_c = "╎";
-        let end_char = "┴";
-        let col_width = 2;
-
-        let (width, _) = termion::terminal_size()?;
-
-        let mut cursor = self.prefix_len as u16;
-        let end_cursor = if width % 2 == 0 { width - 2 } else { width - 1 };
-
-        // A string representation for a row to be printed
-        struct Row {
-            content: String,
-            prefix: Option<String>,
-        }
-        let mut row = Row {
-            content: String::with_capacity(width as usize),
-            prefix: None,
-        };
-
-        // Returns the maximum or minimum value of all available values
-        macro_rules! get_value {
-            ($($p:ident)*) => (
-                $(self.columns.iter().filter_map(|c| {
-                    match *c {
-                        Column::Used(ref line) => line.values.iter().$p(),
-                        _ => None,
-                    }
-                }).$p().cloned().unwrap_or_default())*
-            )
-        }
+    pub fn config_mut(&mut self) -> &mut GraphConfig {
+        &mut self.config
+    }
 
-        // Get the current minimum and maximum values from all lines
-        let (min, max) = (get_value!(min), get_value!(max));
+    /// Replace the configuration of the graph while keeping all lines and
+    /// the print count, which takes effect on the next print
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, GraphConfig};
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line", 1).unwrap();
+    /// graph.apply_config(GraphConfig {
+    ///     sparkline: true,
+    ///     ..GraphConfig::default()
+    /// });
+    /// assert!(graph.config().sparkline);
+    /// ```
+    pub fn apply_config(&mut self, config: GraphConfig) {
+        self.config = config;
 
-        // Gather all columns together
-        for column in &mut self.columns {
-            // Check if we an print more columns
-            if end_cursor < cursor + col_width {
-                row.content += "…";
-                cursor += 1;
-                break;
-            }
+        // The fallback width or resize behavior may have changed
+        self.cached_width = None;
+    }
 
-            // Column can be printed
-            let free_column = match *column {
-                Column::Used(ref mut line) => {
-                    // Get a row prefix format and keep three characters left
-                    let mut row_prefix = format!(
-                        "{:>w$.*}",
-                        self.prefix_len - 3,
-                        line.name,
-                        w = self.prefix_len - 3
-                    );
+    /// Render all lines which received data since the last print in bold
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_bold_latest();
+    /// ```
+    pub fn with_bold_latest(mut self) -> Self {
+        self.config.bold_latest = true;
+        self
+    }
 
-                    // Get the character to be printed
-                    let (c, free_column) = if line.started {
-                        // Check if the line is done an can be used later on
-                        if self.lines_to_be_removed.contains(&line.name) {
-                            row_prefix += " ← ";
-                            row.prefix = Some(row_prefix);
-                            (end_char, true)
-                        } else {
-                            (
-                                if line.got_data { line_chr } else { nodata_c },
-                                false,
-                            )
-                        }
-                    } else {
-                        row_prefix += " → ";
-                        row.prefix = Some(row_prefix);
-                        line.started = true;
-                        (start_ch, false)
-                    };
+    /// Mark every line whose latest value equals the provided axis value
+    /// with a distinct character, which is useful as a baseline for signed
+    /// data
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<i8> = Graph::new().with_axis(0);
+    /// ```
+    pub fn with_axis(mut self, value: V) -> Self {
+        self.axis = Some(value);
+        self
+    }
 
-                    // Get the rgb value for the character
-                    let value = line.values.last().cloned().unwrap_or_default();
-                    let (r, g, b) =
-                        Self::rgb(min.clone(), max.clone(), value.clone());
+    /// Visually group the lines by adding an additional fill character after
+    /// every `every_n_lines` used columns. A value of zero disables the
+    /// grouping.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_group_separator(4);
+    /// ```
+    pub fn with_group_separator(mut self, every_n_lines: usize) -> Self {
+        self.config.group_separator = every_n_lines;
+        self
+    }
 
-                    row.content += &format!(
-                        "{}{}{}",
-                        Fg(color::Rgb(r, g, b)),
-                        c,
-                        Fg(Reset)
-                    );
-                    row.content += &fillchar();
+    /// Adapt the length of the identifier (prefix) to the longest line name
+    /// on every print. The length is capped to 32 characters by default, see
+    /// `with_min_prefix_length` and `with_max_prefix_length` for adapting the
+    /// bounds.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_auto_prefix_length();
+    /// ```
+    pub fn with_auto_prefix_length(mut self) -> Self {
+        self.config.auto_prefix = true;
+        self
+    }
 
-                    // Reset the line indicator for the data
-                    line.got_data = false;
+    /// Set the minimum length of the identifier (prefix) if the automatic
+    /// prefix length is enabled
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new()
+    ///     .with_auto_prefix_length()
+    ///     .with_min_prefix_length(8);
+    /// ```
+    pub fn with_min_prefix_length(mut self, length: usize) -> Self {
+        self.config.min_prefix_len = length;
+        self
+    }
 
-                    free_column
-                }
-                Column::Free => {
-                    row.content += &fillchar();
-                    row.content += &fillchar();
-                    false
-                }
-            };
-            if free_column {
-                *column = Column::Free;
-            }
+    /// Set the maximum length of the identifier (prefix) if the automatic
+    /// prefix length is enabled
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new()
+    ///     .with_auto_prefix_length()
+    ///     .with_max_prefix_length(16);
+    /// ```
+    pub fn with_max_prefix_length(mut self, length: usize) -> Self {
+        self.config.max_prefix_len = length;
+        self
+    }
 
-            cursor += col_width;
-        }
+    /// Left align the identifier text within the prefix column
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_left_aligned_prefix();
+    /// ```
+    pub fn with_left_aligned_prefix(self) -> Self {
+        self.with_label_alignment(LabelAlignment::Left)
+    }
 
-        // Fill rest of the screen
-        for _ in cursor..width {
-            row.content += &fillchar();
-        }
+    /// Right align the identifier text within the prefix column, which is
+    /// the default
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_right_aligned_prefix();
+    /// ```
+    pub fn with_right_aligned_prefix(self) -> Self {
+        self.with_label_alignment(LabelAlignment::Right)
+    }
 
-        // Print the row including the prefix if set
-        let prefix_string = match row.prefix {
-            Some(prefix) => prefix,
-            _ => iter::repeat(' ').take(self.prefix_len).collect::<String>(),
-        };
-        println!("{}{}", prefix_string, row.content);
+    /// Set the alignment of the identifier text within the prefix column
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, LabelAlignment};
+    ///
+    /// let _: Graph<u8> =
+    ///     Graph::new().with_label_alignment(LabelAlignment::Center);
+    /// ```
+    pub fn with_label_alignment(mut self, alignment: LabelAlignment) -> Self {
+        self.config.label_alignment = alignment;
+        self
+    }
 
-        // Cleanup lines to be removed
-        self.lines_to_be_removed.clear();
-        Ok(())
+    /// Hide the identifier (prefix) column including the start and end
+    /// markers
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_no_prefix();
+    /// ```
+    pub fn with_no_prefix(mut self) -> Self {
+        self.config.prefix_len = 0;
+        self.config.show_prefix = false;
+        self
     }
 
-    /// Print only if new data is available. Returns an indicator if somethings
-    /// was printed or not.
+    /// Prepend the current wall clock time to every printed row. The default
+    /// format is `%H:%M:%S`, which can be changed via `with_timestamp_format`.
     ///
     /// # Example
     /// ```
     /// use rain::Graph;
     ///
-    /// let mut graph = Graph::new();
+    /// let _: Graph<u8> = Graph::new().with_timestamp_prefix();
+    /// ```
+    pub fn with_timestamp_prefix(mut self) -> Self {
+        if self.config.timestamp_format.is_none() {
+            self.config.timestamp_format = Some("%H:%M:%S".to_owned());
+        }
+        self
+    }
+
+    /// Prepend the current wall clock time in a custom `strftime` like
+    /// format to every printed row
     ///
-    /// assert!(graph.add("Line 1", 0).is_ok());
-    /// assert!(graph.add("Line 2", 0).is_ok());
+    /// # Example
+    /// ```
+    /// use rain::Graph;
     ///
-    /// graph.print_if_new_data();
-    /// graph.print_if_new_data();
+    /// let _: Graph<u8> = Graph::new().with_timestamp_format("%H:%M:%S%.3f");
     /// ```
-    pub fn print_if_new_data(&mut self) -> Fallible<bool> {
-        if !self.lines_to_be_removed.is_empty()
+    pub fn with_timestamp_format(mut self, format: &str) -> Self {
+        self.config.timestamp_format = Some(format.to_owned());
+        self
+    }
+
+    /// Prepend the zero padded amount of printed rows (ticks) to every
+    /// printed row
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_tick_counter_prefix();
+    /// ```
+    pub fn with_tick_counter_prefix(mut self) -> Self {
+        self.config.tick_counter = true;
+        self
+    }
+
+    /// Prepend the four digit zero padded row number to every printed row,
+    /// behind the timestamp if enabled
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_row_numbers();
+    /// ```
+    pub fn with_row_numbers(mut self) -> Self {
+        self.config.row_numbers = true;
+        self
+    }
+
+    /// Print the graph to stderr instead of stdout
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_stderr_output();
+    /// ```
+    pub fn with_stderr_output(self) -> Self {
+        self.with_output(OutputTarget::Stderr)
+    }
+
+    /// Set the target where the graph will be printed to
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, OutputTarget};
+    /// use std::io;
+    ///
+    /// let _: Graph<u8> =
+    ///     Graph::new().with_output(OutputTarget::Custom(Box::new(io::sink())));
+    /// ```
+    pub fn with_output(mut self, output: OutputTarget) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Limit the printing to at most one row per provided interval. The
+    /// `print_if_new_data` method skips the printing if the interval has not
+    /// elapsed yet, whereas `print` blocks until it elapsed.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    /// use std::time::Duration;
+    ///
+    /// let _: Graph<u8> =
+    ///     Graph::new().with_rate_limit(Duration::from_millis(33));
+    /// ```
+    pub fn with_rate_limit(mut self, min_interval: Duration) -> Self {
+        self.config.rate_limit = Some(min_interval);
+        self
+    }
+
+    /// Defer the printing of `print_if_new_data` until no new data was added
+    /// for the provided quiet period
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    /// use std::time::Duration;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_debounce(Duration::from_millis(50));
+    /// ```
+    pub fn with_debounce(mut self, quiet_period: Duration) -> Self {
+        self.config.debounce = Some(quiet_period);
+        self
+    }
+
+    /// Use the provided terminal width if the output is not a terminal, for
+    /// example if it is piped or a custom writer, or if the terminal size can
+    /// not be determined
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_fallback_size(80);
+    /// ```
+    pub fn with_fallback_size(mut self, width: u16) -> Self {
+        self.config.fallback_width = Some(width);
+        self
+    }
+
+    /// Determine the terminal size only once on the first print and reuse it
+    /// afterwards. Use `force_resize_check` to measure the terminal again.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_no_resize();
+    /// ```
+    pub fn with_no_resize(mut self) -> Self {
+        self.config.no_resize = true;
+        self
+    }
+
+    /// Collect rendering performance metrics on every print, which can be
+    /// retrieved via `metrics`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> = Graph::new().with_metrics_enabled();
+    /// assert!(graph.metrics().is_some());
+    /// ```
+    pub fn with_metrics_enabled(mut self) -> Self {
+        self.metrics = Some(GraphMetrics::default());
+        self
+    }
+
+    /// Limit the amount of simultaneously used columns. Adding a new line
+    /// which exceeds the limit results in an error.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new().with_max_columns(1);
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert!(graph.add("Line 2", 0).is_err());
+    /// ```
+    pub fn with_max_columns(mut self, limit: usize) -> Self {
+        self.config.max_columns = Some(limit);
+        self
+    }
+
+    /// Set the behavior if the columns do not fit into the terminal
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, OverflowPolicy};
+    ///
+    /// let _: Graph<u8> = Graph::new().with_overflow_policy(OverflowPolicy::Scroll);
+    /// ```
+    pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+        self.config.overflow_policy = policy;
+        self
+    }
+
+    /// Set the character which indicates columns which do not fit into the
+    /// terminal, which fails if the character is not exactly one column wide
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// assert!(Graph::<u8>::new().with_overflow_char('>').is_ok());
+    /// assert!(Graph::<u8>::new().with_overflow_char('界').is_err());
+    /// ```
+    pub fn with_overflow_char(mut self, c: char) -> RainResult<Self> {
+        check_char_width(c)?;
+        self.config.overflow_char = c;
+        Ok(self)
+    }
+
+    /// Set the character which fills the space between the lines, which
+    /// fails if the character is not exactly one column wide
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// assert!(Graph::<u8>::new().with_separator_char('·').is_ok());
+    /// assert!(Graph::<u8>::new().with_separator_char('界').is_err());
+    /// ```
+    pub fn with_separator_char(mut self, c: char) -> RainResult<Self> {
+        check_char_width(c)?;
+        self.config.fill_char = c;
+        Ok(self)
+    }
+
+    /// Set the marker behind the identifier of a newly added line, which
+    /// fails if the marker is not exactly three columns wide
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// assert!(Graph::<u8>::new().with_start_marker(" > ").is_ok());
+    /// assert!(Graph::<u8>::new().with_start_marker(">").is_err());
+    /// ```
+    pub fn with_start_marker(mut self, marker: &str) -> RainResult<Self> {
+        check_marker_width(marker)?;
+        self.config.start_marker = marker.to_owned();
+        Ok(self)
+    }
+
+    /// Set the marker behind the identifier of a removed line, which fails
+    /// if the marker is not exactly three columns wide
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// assert!(Graph::<u8>::new().with_end_marker(" < ").is_ok());
+    /// assert!(Graph::<u8>::new().with_end_marker("<").is_err());
+    /// ```
+    pub fn with_end_marker(mut self, marker: &str) -> RainResult<Self> {
+        check_marker_width(marker)?;
+        self.config.end_marker = marker.to_owned();
+        Ok(self)
+    }
+
+    /// Print a title centered above the first row of the graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_title("Dashboard");
+    /// ```
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.config.title = Some(title.to_owned());
+        self
+    }
+
+    /// Print the names of the lines above the first row of the graph, each
+    /// truncated to the width of a single column
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_column_labels();
+    /// ```
+    pub fn with_column_labels(mut self) -> Self {
+        self.config.column_labels = true;
+        self
+    }
+
+    /// Use a single character per column without the trailing fill
+    /// character, which fits twice as many lines into the terminal
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_compact_mode();
+    /// ```
+    pub fn with_compact_mode(mut self) -> Self {
+        self.config.compact = true;
+        self
+    }
+
+    /// Print the graph immediately whenever a line gets removed, which shows
+    /// the end marker without an additional call to `print()`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_print_on_remove(true);
+    /// ```
+    pub fn with_print_on_remove(mut self, enabled: bool) -> Self {
+        self.config.print_on_remove = enabled;
+        self
+    }
+
+    /// Print the graph immediately whenever a new line gets added, which
+    /// shows the start marker without an additional call to `print()`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_print_on_add(true);
+    /// ```
+    pub fn with_print_on_add(mut self, enabled: bool) -> Self {
+        self.config.print_on_add = enabled;
+        self
+    }
+
+    /// Always add new lines to the right instead of reusing the columns of
+    /// removed lines, which keeps the order of the lines stable at the cost
+    /// of unused columns
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_lock_order();
+    /// ```
+    pub fn with_lock_order(mut self) -> Self {
+        self.config.lock_order = true;
+        self
+    }
+
+    /// Remove the columns of removed lines on print, so that no gaps remain
+    /// between the lines at the cost of shifting their positions
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_compact_removed();
+    /// ```
+    pub fn with_compact_removed(mut self) -> Self {
+        self.config.compact_removed = true;
+        self
+    }
+
+    /// Keep the time of every added value, which enables time based queries
+    /// like `time_windowed_values`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_time_series();
+    /// ```
+    pub fn with_time_series(mut self) -> Self {
+        self.config.time_series = true;
+        self
+    }
+
+    /// Display only the values which have been added within the last
+    /// `duration`, which lets the colors follow the recent values. Older
+    /// values are still kept for the export. This enables the time series,
+    /// too.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    /// use std::time::Duration;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_time_window(Duration::from_secs(60));
+    /// ```
+    pub fn with_time_window(mut self, duration: Duration) -> Self {
+        self.config.time_series = true;
+        self.config.time_window = Some(duration);
+        self
+    }
+
+    /// Insert the provided amount of additional fill characters between
+    /// adjacent lines
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_column_gap(1);
+    /// ```
+    pub fn with_column_gap(mut self, gap: usize) -> Self {
+        self.config.column_gap = gap;
+        self
+    }
+
+    /// Insert the provided separator between adjacent lines
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_line_separator("|");
+    /// ```
+    pub fn with_line_separator(mut self, separator: &str) -> Self {
+        self.config.line_separator = separator.to_owned();
+        self
+    }
+
+    /// Pass all errors of `print` and `print_if_new_data` to the provided
+    /// handler instead of returning them. The methods return successfully
+    /// afterwards, whereas `print_if_new_data` indicates that nothing was
+    /// printed.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> =
+    ///     Graph::new().with_error_handler(|e| eprintln!("graph error: {}", e));
+    /// ```
+    pub fn with_error_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(RainError) + Send + 'static,
+    {
+        self.error_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Call the provided hook after every printed row, when the print state
+    /// of the graph has already been updated
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new()
+    ///     .with_print_hook(|graph| eprintln!("printed {}", graph.print_count()));
+    /// ```
+    pub fn with_print_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Graph<V>) + Send + 'static,
+    {
+        self.print_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Use the xterm 256 color palette instead of true colors for terminals
+    /// which do not support them
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_256_color_mode();
+    /// ```
+    pub fn with_256_color_mode(mut self) -> Self {
+        self.config.color_mode = ColorMode::Ansi256;
+        self
+    }
+
+    /// Use only the 8 basic ANSI colors for maximum compatibility
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_8_color_mode();
+    /// ```
+    pub fn with_8_color_mode(mut self) -> Self {
+        self.config.color_mode = ColorMode::Ansi8;
+        self
+    }
+
+    /// Encode the latest value of every line as block character (`▁` to `█`)
+    /// additionally to its color
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_sparkline_mode();
+    /// ```
+    pub fn with_sparkline_mode(mut self) -> Self {
+        self.config.sparkline = true;
+        self
+    }
+
+    /// Encode the latest four values of every line as braille character,
+    /// whereas every dot from top to bottom represents one value. A dot is
+    /// active if the value is above or equal to the median of the line.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_braille_mode();
+    /// ```
+    pub fn with_braille_mode(mut self) -> Self {
+        self.config.braille = true;
+        self
+    }
+
+    /// Color the lines by the difference between their latest two values
+    /// instead of the raw values, which is useful for cumulative counters.
+    /// Lines with only one value have a difference of zero.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u32> = Graph::new().with_derivative_mode();
+    /// ```
+    pub fn with_derivative_mode(mut self) -> Self {
+        self.config.derivative = true;
+        self
+    }
+
+    /// Keep the color scale fixed for the first `n` prints, which prevents
+    /// initial outliers from skewing the colors. All lines use the color of
+    /// the lowest value during that time, and the lowest block if the
+    /// sparkline mode is enabled.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u32> = Graph::new().with_auto_scale_delay(5);
+    /// ```
+    pub fn with_auto_scale_delay(mut self, n: usize) -> Self {
+        self.config.auto_scale_delay = n;
+        self
+    }
+
+    /// Scale the colors of every line by its own minimum and maximum value
+    /// instead of the ones of all lines
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u32> = Graph::new().with_per_line_scale();
+    /// ```
+    pub fn with_per_line_scale(mut self) -> Self {
+        self.config.per_line_scale = true;
+        self
+    }
+
+    /// Store the running sum of all added values per line instead of the
+    /// values itself. The sum saturates at the numeric bounds of the value
+    /// type.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new().with_cumulative_mode();
+    /// graph.add("Line 1", 200).unwrap();
+    /// graph.add("Line 1", 200).unwrap();
+    /// assert_eq!(&graph["Line 1"], &[200, 255]);
+    /// ```
+    pub fn with_cumulative_mode(mut self) -> Self
+    where
+        V: SaturatingAdd,
+    {
+        self.accumulate = Some(SaturatingAdd::saturating_add);
+        self
+    }
+
+    /// Clamp every added value into the provided bounds before storing it
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new().with_value_clamping(0, 100);
+    /// ```
+    pub fn with_value_clamping(mut self, min: V, max: V) -> Self {
+        self.clamping = Some((min, max));
+        self
+    }
+
+    /// Return an error from `add` if a value had to be clamped. The clamped
+    /// value is stored nevertheless.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new()
+    ///     .with_value_clamping(0, 100)
+    ///     .with_strict_clamping();
+    /// assert!(graph.add("Line 1", 50).is_ok());
+    /// assert!(graph.add("Line 1", 150).is_err());
+    /// ```
+    pub fn with_strict_clamping(mut self) -> Self {
+        self.config.strict_clamping = true;
+        self
+    }
+
+    /// Transform every added value before storing it, for example to convert
+    /// units
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u32> = Graph::new().with_value_transform(|v| v / 1024);
+    /// ```
+    pub fn with_value_transform<F>(mut self, f: F) -> Self
+    where
+        F: Fn(V) -> V + Send + 'static,
+    {
+        self.transform = Some(Box::new(f));
+        self
+    }
+
+    /// Set the format of the textual representation of values, which fails
+    /// if the format is not supported. The format contains a single Rust
+    /// style placeholder with an optional alignment, sign (`+`), zero padding
+    /// (`0`), width and precision, like `{:>6.2}` or `{:+06.2}`, and optional
+    /// surrounding text. Other flags, fill characters and types are rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> =
+    ///     Graph::new().with_value_display_format("{:>6.2}%").unwrap();
+    /// assert_eq!(graph.format_value(5), "  5.00%");
+    ///
+    /// let graph: Graph<u8> =
+    ///     Graph::new().with_value_display_format("{:+06.2}").unwrap();
+    /// assert_eq!(graph.format_value(5), "+05.00");
+    ///
+    /// assert!(Graph::<u8>::new().with_value_display_format("%.2f").is_err());
+    /// assert!(Graph::<u8>::new().with_value_display_format("{:#6}").is_err());
+    /// ```
+    pub fn with_value_display_format(
+        mut self,
+        format: &str,
+    ) -> RainResult<Self> {
+        match ValueFormat::parse(format) {
+            Some(value_format) => {
+                self.config.value_format = value_format;
+                Ok(self)
+            }
+            None => bail!(
+                ErrorType::InvalidFormat {
+                    format: format.to_owned()
+                },
+                "Unsupported value format '{}'",
+                format
+            ),
+        }
+    }
+
+    /// Returns the textual representation of a value according to the
+    /// configured value display format
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> = Graph::new();
+    /// assert_eq!(graph.format_value(5), "5");
+    /// ```
+    pub fn format_value(&self, value: V) -> String {
+        self.config.value_format.format(f64::from(value))
+    }
+
+    /// Group the lines with the provided identifiers logically. The group
+    /// name is shown in front of the line names and adjacent groups are
+    /// separated by an additional fill character.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new()
+    ///     .with_group("CPU", &["cpu0", "cpu1"])
+    ///     .with_group("Network", &["eth0", "wlan0"]);
+    /// ```
+    pub fn with_group(
+        mut self,
+        group_name: &str,
+        identifiers: &[&str],
+    ) -> Self {
+        self.config.groups.push(Group {
+            name: group_name.to_owned(),
+            line_names: identifiers.iter().map(|i| (*i).to_owned()).collect(),
+            color: None,
+        });
+        self
+    }
+
+    /// Color all lines of the group uniformly instead of using the value
+    /// gradient. The group will be created if it does not exist.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let _: Graph<u8> = Graph::new()
+    ///     .with_group("Memory", &["used", "cached"])
+    ///     .with_group_color("Memory", (0, 0, 255));
+    /// ```
+    pub fn with_group_color(
+        mut self,
+        group_name: &str,
+        rgb: (u8, u8, u8),
+    ) -> Self {
+        match self.config.groups.iter_mut().find(|g| g.name == group_name) {
+            Some(group) => group.color = Some(rgb),
+            None => self.config.groups.push(Group {
+                name: group_name.to_owned(),
+                line_names: vec![],
+                color: Some(rgb),
+            }),
+        }
+        self
+    }
+
+    /// Set the global log level for reporting
+    #[cfg(feature = "mowl")]
+    #[cfg_attr(
+        feature = "tracing",
+        deprecated(note = "configure a tracing subscriber instead")
+    )]
+    pub fn set_log_level(self, level: LevelFilter) -> Self {
+        // Setup the logger if not already set
+        if mowl::init_with_level(level).is_err() {
+            log::warn!("Logger already set.");
+        } else {
+            log::info!("Log level set to: {:?}", level);
+        }
+        self
+    }
+
+    /// Add a data value to the graph by some identifier which can be displayed
+    /// somehow.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let line = graph.add("Line 1", 0).unwrap();
+    ///
+    /// assert_eq!(line, "Line 1");
+    /// ```
+    pub fn add<T>(&mut self, identifier: T, value: V) -> RainResult<T>
+    where
+        T: fmt::Display,
+    {
+        // Get a line name string from the identifier
+        let line_name = format!("{}", identifier);
+        debug!("Adding value {:?} to line '{}'", value, line_name);
+        self.last_add = Some(Instant::now());
+
+        // Transform the value if needed, whereas line specific transformations
+        // take precedence
+        let line_transform = self
+            .find_line(&line_name)
+            .and_then(|line| line.transform.clone());
+        let value = match (line_transform, &self.transform) {
+            (Some(Transform(f)), _) => f(value),
+            (None, Some(f)) => f(value),
+            (None, None) => value,
+        };
+
+        // Clamp the value into the bounds if needed
+        let mut clamped = false;
+        let value = match self.clamping {
+            Some((ref min, ref max)) if value < *min || value > *max => {
+                debug!("Clamping value into [{:?}, {:?}]", min, max);
+                clamped = true;
+                if value < *min {
+                    min.clone()
+                } else {
+                    max.clone()
+                }
+            }
+            _ => value,
+        };
+
+        // Keep the time of the value if timestamps are enabled
+        let timestamp = self.timestamp();
+
+        // Just add the value if the line already exist
+        let accumulate = self.accumulate;
+        let add_new_line = {
+            if let Some(line) = self.line_already_existing(&line_name) {
+                debug!("Line already exist, just adding the value");
+                let value = match (accumulate, line.values.last()) {
+                    (Some(f), Some(sum)) => f(sum.clone(), value.clone()),
+                    _ => value.clone(),
+                };
+                line.add_timed_value(value, timestamp);
+                false
+            } else {
+                true
+            }
+        };
+
+        // Add a new line and set the column as used
+        if add_new_line {
+            self.check_max_columns(1)?;
+
+            debug!("Adding new line");
+            let column = self.get_next_free_column();
+            let mut line = Line::new(&line_name);
+            line.add_timed_value(value, timestamp);
+            *column = Column::Used(line);
+
+            if self.config.print_on_add {
+                self.print()?;
+            }
+        }
+
+        if clamped && self.config.strict_clamping {
+            bail!(
+                ErrorType::ValueClamped { name: line_name },
+                "Value exceeded the bounds and has been clamped"
+            );
+        }
+
+        Ok(identifier)
+    }
+
+    /// Insert a pre-built line into the next free column of the graph, like
+    /// adding a new line does. The maximum amount of columns and the locked
+    /// order of the columns are respected.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, Line};
+    ///
+    /// let mut line = Line::new("Line 1");
+    /// line.add_value(1);
+    /// line.add_value(2);
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.insert_line(line).is_ok());
+    /// assert_eq!(graph.line_values("Line 1"), Some(&[1, 2][..]));
+    /// ```
+    pub fn insert_line(&mut self, line: Line<V>) -> RainResult<()> {
+        if self.find_line(&line.name).is_some() {
+            bail!(
+                ErrorType::LineAlreadyExists { name: line.name },
+                "Line does already exist and can not be inserted"
+            );
+        }
+        self.check_max_columns(1)?;
+
+        debug!("Inserting line '{}'", line.name);
+        *self.get_next_free_column() = Column::Used(line);
+
+        if self.config.print_on_add {
+            self.print()?;
+        }
+        Ok(())
+    }
+
+    /// Add all values of the provided stream to the graph by some identifier
+    /// until the stream is exhausted
+    ///
+    /// # Example
+    /// ```
+    /// use futures::{executor::block_on, stream};
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let values = stream::iter(vec![0, 5, 10]);
+    /// let line = block_on(graph.add_stream("Line 1", values)).unwrap();
+    ///
+    /// assert_eq!(line, "Line 1");
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn add_stream<T, S>(
+        &mut self,
+        identifier: T,
+        stream: S,
+    ) -> RainResult<T>
+    where
+        T: fmt::Display,
+        S: Stream<Item = V>,
+    {
+        pin_mut!(stream);
+        while let Some(value) = stream.next().await {
+            self.add(&identifier, value)?;
+        }
+        Ok(identifier)
+    }
+
+    /// Remove a line from the graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let line = graph.add("Line 1", 0).unwrap();
+    ///
+    /// let removed_line = graph.remove(line).unwrap();
+    /// assert_eq!(removed_line, "Line 1");
+    /// ```
+    pub fn remove<T>(&mut self, identifier: T) -> RainResult<T>
+    where
+        T: fmt::Display,
+    {
+        // Check if the line exists
+        let line_name = format!("{}", identifier);
+        if self.line_already_existing(&line_name).is_none() {
+            bail!(
+                ErrorType::LineDoesNotExist { name: line_name },
+                "Line does not exist and can not be removed"
+            );
+        }
+
+        // Check if the line is already about to be removed
+        if self.lines_to_be_removed.contains(&line_name) {
+            bail!(
+                ErrorType::DuplicateRemoval { name: line_name },
+                "Line is already about to be removed"
+            );
+        }
+
+        // Just push the line into a temporarily vector
+        self.lines_to_be_removed.push(line_name);
+
+        if self.config.print_on_remove {
+            self.print()?;
+        }
+
+        Ok(identifier)
+    }
+
+    /// Merge the source line into the destination line by appending the
+    /// element wise results of `f(source_value, destination_value)` to the
+    /// destination line. Only as many values as both lines contain will be
+    /// merged. The source line is removed afterwards. The graph stays
+    /// untouched if one of both lines is already about to be removed.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.add("rx_bytes", 10).is_ok());
+    /// assert!(graph.add("tx_bytes", 20).is_ok());
+    ///
+    /// assert!(graph.merge_lines("rx_bytes", "tx_bytes", |a, b| a + b).is_ok());
+    /// ```
+    pub fn merge_lines<T, F>(&mut self, src: T, dst: T, f: F) -> RainResult<()>
+    where
+        T: fmt::Display,
+        F: Fn(V, V) -> V,
+    {
+        let src_name = format!("{}", src);
+        let dst_name = format!("{}", dst);
+        if src_name == dst_name {
+            bail!(ErrorType::Other, "Line can not be merged into itself");
+        }
+
+        let src_values = match self.find_line(&src_name) {
+            Some(line) => line.values.clone(),
+            None => bail!(
+                ErrorType::LineDoesNotExist { name: src_name },
+                "Source line does not exist and can not be merged"
+            ),
+        };
+        if self.find_line(&dst_name).is_none() {
+            bail!(
+                ErrorType::LineDoesNotExist { name: dst_name },
+                "Destination line does not exist and can not be merged"
+            );
+        }
+
+        // Check both lines before touching the destination line
+        if self.lines_to_be_removed.contains(&src_name) {
+            bail!(
+                ErrorType::DuplicateRemoval { name: src_name },
+                "Source line is already about to be removed"
+            );
+        }
+        if self.lines_to_be_removed.contains(&dst_name) {
+            bail!(
+                ErrorType::LineDoesNotExist { name: dst_name },
+                "Destination line is about to be removed and can not be merged"
+            );
+        }
+
+        let timestamp = self.timestamp();
+        if let Some(line) = self.line_already_existing(&dst_name) {
+            debug!("Merging line '{}' into '{}'", src_name, dst_name);
+            let merged: Vec<V> = src_values
+                .into_iter()
+                .zip(line.values.iter().cloned())
+                .map(|(s, d)| f(s, d))
+                .collect();
+            for value in merged {
+                line.add_timed_value(value, timestamp);
+            }
+        }
+
+        self.remove(src_name)?;
+        Ok(())
+    }
+
+    /// Combine the lines of both graphs by adding all active lines of the
+    /// other graph, whereas the values of lines which exist in both graphs
+    /// are appended to the existing line. The configuration of this graph
+    /// will be kept, which fails if the additional lines would exceed its
+    /// maximum amount of columns.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut first = Graph::new();
+    /// first.add("CPU", 1).unwrap();
+    ///
+    /// let mut second = Graph::new();
+    /// second.add("CPU", 2).unwrap();
+    /// second.add("RAM", 3).unwrap();
+    ///
+    /// let merged = first.merge(second).unwrap();
+    /// assert_eq!(merged.line_values("CPU"), Some(&[1, 2][..]));
+    /// assert_eq!(merged.line_values("RAM"), Some(&[3][..]));
+    /// ```
+    pub fn merge(mut self, other: Graph<V>) -> RainResult<Graph<V>> {
+        let new_lines = other
+            .columns
+            .iter()
+            .filter(|c| match **c {
+                Column::Used(ref line) => {
+                    !other.lines_to_be_removed.contains(&line.name)
+                        && self.find_line(&line.name).is_none()
+                }
+                Column::Free => false,
+            })
+            .count();
+        self.check_max_columns(new_lines)?;
+
+        for column in other.columns {
+            match column {
+                Column::Used(line)
+                    if !other.lines_to_be_removed.contains(&line.name) =>
+                {
+                    debug!("Merging line '{}' into the graph", line.name);
+                    match self.line_already_existing(&line.name) {
+                        Some(existing) => existing.append(line),
+                        None => {
+                            *self.get_next_free_column() = Column::Used(line)
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(self)
+    }
+
+    /// Swap the column positions of two lines without changing their data
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    ///
+    /// assert!(graph.swap_lines("Line 1", "Line 2").is_ok());
+    /// assert_eq!(graph.all_lines(), vec!["Line 2", "Line 1"]);
+    /// ```
+    pub fn swap_lines<T>(&mut self, a: T, b: T) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        let a_index = self.existing_column_index(a)?;
+        let b_index = self.existing_column_index(b)?;
+        self.columns.swap(a_index, b_index);
+        Ok(())
+    }
+
+    /// Move the line into the first column without changing its data. The
+    /// previous column stays free if the order of the lines is locked.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    ///
+    /// assert!(graph.move_to_front("Line 2").is_ok());
+    /// assert_eq!(graph.all_lines(), vec!["Line 2", "Line 1"]);
+    /// ```
+    pub fn move_to_front<T>(&mut self, identifier: T) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        let index = self.existing_column_index(identifier)?;
+        let column = self.take_column(index);
+        self.columns.insert(0, column);
+        Ok(())
+    }
+
+    /// Move the line into the last column without changing its data. The
+    /// previous column stays free if the order of the lines is locked.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    ///
+    /// assert!(graph.move_to_back("Line 1").is_ok());
+    /// assert_eq!(graph.all_lines(), vec!["Line 2", "Line 1"]);
+    /// ```
+    pub fn move_to_back<T>(&mut self, identifier: T) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        let index = self.existing_column_index(identifier)?;
+        let column = self.take_column(index);
+        self.columns.push(column);
+        Ok(())
+    }
+
+    /// Sort the lines by the key which gets calculated from the name and the
+    /// values of every line, whereas free columns are moved to the end
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 5).unwrap();
+    /// graph.add("Line 2", 3).unwrap();
+    ///
+    /// graph.sort_lines_by_key(|_, values| values.last().cloned());
+    /// assert_eq!(graph.all_lines(), vec!["Line 2", "Line 1"]);
+    /// ```
+    pub fn sort_lines_by_key<F, K>(&mut self, f: F)
+    where
+        F: Fn(&str, &[V]) -> K,
+        K: Ord,
+    {
+        self.columns.sort_by_key(|c| match *c {
+            Column::Used(ref line) => {
+                (false, Some(f(&line.name, &line.values)))
+            }
+            Column::Free => (true, None),
+        });
+    }
+
+    /// Transform every value added to the line before storing it, which
+    /// overrides the global `with_value_transform` for this line
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.add("Line 1", 1000).is_ok());
+    ///
+    /// assert!(graph.with_per_line_transform("Line 1", |v| v / 1000).is_ok());
+    /// assert!(graph.with_per_line_transform("Line 2", |v| v).is_err());
+    /// ```
+    pub fn with_per_line_transform<T, F>(
+        &mut self,
+        identifier: T,
+        f: F,
+    ) -> RainResult<()>
+    where
+        T: fmt::Display,
+        F: Fn(V) -> V + Send + Sync + 'static,
+    {
+        let line_name = format!("{}", identifier);
+        match self.line_already_existing(&line_name) {
+            Some(line) => {
+                debug!("Setting transformation of line '{}'", line_name);
+                line.transform = Some(Transform(Arc::new(f)));
+                Ok(())
+            }
+            None => bail!(
+                ErrorType::LineDoesNotExist { name: line_name },
+                "Line does not exist and can not be transformed"
+            ),
+        }
+    }
+
+    /// Add a line to an existing group
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new().with_group("CPU", &["cpu0"]);
+    ///
+    /// assert!(graph.add_to_group("CPU", "cpu1").is_ok());
+    /// assert!(graph.add_to_group("Network", "eth0").is_err());
+    /// ```
+    pub fn add_to_group<T>(
+        &mut self,
+        group_name: &str,
+        identifier: T,
+    ) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        match self.config.groups.iter_mut().find(|g| g.name == group_name) {
+            Some(group) => {
+                group.line_names.push(format!("{}", identifier));
+                Ok(())
+            }
+            None => bail!(
+                ErrorType::GroupDoesNotExist {
+                    name: group_name.to_owned()
+                },
+                "Group does not exist and the line can not be added"
+            ),
+        }
+    }
+
+    /// Highlight a line, which renders its characters in bold until
+    /// `unhighlight_line` is called
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let line = graph.add("Line 1", 0).unwrap();
+    ///
+    /// assert!(graph.highlight_line(line).is_ok());
+    /// assert!(graph.highlight_line("Line 2").is_err());
+    /// ```
+    pub fn highlight_line<T>(&mut self, identifier: T) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        self.set_highlighted(identifier, true)
+    }
+
+    /// Remove the highlighting from a line
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// let line = graph.add("Line 1", 0).unwrap();
+    ///
+    /// assert!(graph.highlight_line(line).is_ok());
+    /// assert!(graph.unhighlight_line(line).is_ok());
+    /// ```
+    pub fn unhighlight_line<T>(&mut self, identifier: T) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        self.set_highlighted(identifier, false)
+    }
+
+    /// Set the alignment of the identifier text of a single line, which
+    /// overrides the alignment of the graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, LabelAlignment};
+    ///
+    /// let mut graph = Graph::new();
+    /// let line = graph.add("Line 1", 0).unwrap();
+    ///
+    /// assert!(graph.set_line_alignment(line, LabelAlignment::Left).is_ok());
+    /// ```
+    pub fn set_line_alignment<T>(
+        &mut self,
+        identifier: T,
+        alignment: LabelAlignment,
+    ) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        let line_name = format!("{}", identifier);
+        match self.line_already_existing(&line_name) {
+            Some(line) => {
+                line.alignment = Some(alignment);
+                Ok(())
+            }
+            None => bail!(
+                ErrorType::LineDoesNotExist { name: line_name },
+                "Line does not exist and can not be aligned"
+            ),
+        }
+    }
+
+    /// Returns the zero-based index of the column which contains the line
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    ///
+    /// assert_eq!(graph.column_index("Line 2"), Some(1));
+    /// assert_eq!(graph.column_index("Line 3"), None);
+    /// ```
+    pub fn column_index<T>(&self, identifier: T) -> Option<usize>
+    where
+        T: fmt::Display,
+    {
+        let line_name = identifier.to_string();
+        self.columns.iter().position(|c| match *c {
+            Column::Used(ref line) => line.name == line_name,
+            Column::Free => false,
+        })
+    }
+
+    /// Returns the names of all lines in column order, excluding the ones
+    /// which are about to be removed
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    /// graph.remove("Line 1").unwrap();
+    ///
+    /// assert_eq!(graph.active_lines(), vec!["Line 2"]);
+    /// assert_eq!(graph.all_lines(), vec!["Line 1", "Line 2"]);
+    /// ```
+    pub fn active_lines(&self) -> Vec<String> {
+        self.all_lines()
+            .into_iter()
+            .filter(|name| !self.lines_to_be_removed.contains(name))
+            .collect()
+    }
+
+    /// Returns the names of all lines in column order, including the ones
+    /// which are about to be removed
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// assert_eq!(graph.all_lines(), vec!["Line 1"]);
+    /// ```
+    pub fn all_lines(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) => Some(line.name.clone()),
+                Column::Free => None,
+            })
+            .collect()
+    }
+
+    /// Returns an iterator over the names and values of all lines in column
+    /// order
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    ///
+    /// for (name, values) in graph.columns_iter() {
+    ///     assert_eq!(name, "Line 1");
+    ///     assert_eq!(values, &[1, 2]);
+    /// }
+    /// ```
+    pub fn columns_iter(&self) -> impl Iterator<Item = (&str, &[V])> {
+        self.columns.iter().filter_map(|c| match *c {
+            Column::Used(ref line) => {
+                Some((line.name.as_str(), line.values.as_slice()))
+            }
+            Column::Free => None,
+        })
+    }
+
+    /// Returns an iterator over the names and mutable values of all lines in
+    /// column order
+    ///
+    /// Modifying the values does not mark the lines as having received new
+    /// data, which means that `print_if_new_data` will not print because of
+    /// it.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    ///
+    /// for (_, mut values) in graph.columns_iter_mut() {
+    ///     values.iter_mut().for_each(|v| *v *= 10);
+    /// }
+    /// assert_eq!(graph.value_range(), Some((10, 10)));
+    /// ```
+    pub fn columns_iter_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (&str, ValuesMut<'_, V>)> {
+        let timestamp = self.timestamp();
+        self.columns.iter_mut().filter_map(move |c| match *c {
+            Column::Used(Line {
+                ref name,
+                ref mut values,
+                ref mut timestamps,
+                ..
+            }) => Some((
+                name.as_str(),
+                ValuesMut {
+                    values,
+                    timestamps,
+                    timestamp,
+                },
+            )),
+            Column::Free => None,
+        })
+    }
+
+    /// Returns true if the graph does not contain any lines
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.is_empty());
+    ///
+    /// graph.add("Line 1", 0).unwrap();
+    /// assert!(!graph.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.used_columns() == 0
+    }
+
+    /// Returns the current minimum and maximum value of all lines, or `None`
+    /// if no values exist
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert_eq!(graph.value_range(), None);
+    ///
+    /// graph.add("Line 1", 5).unwrap();
+    /// graph.add("Line 2", 10).unwrap();
+    /// assert_eq!(graph.value_range(), Some((5, 10)));
+    /// ```
+    pub fn value_range(&self) -> Option<(V, V)> {
+        let has_values = self.columns.iter().any(|c| match *c {
+            Column::Used(ref line) => !line.values.is_empty(),
+            Column::Free => false,
+        });
+        if has_values {
+            Some(self.value_bounds())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the amount of prints without new data since the line received
+    /// its latest value
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new().with_fallback_size(80);
+    /// graph.add("Line 1", 0).unwrap();
+    /// assert_eq!(graph.line_age("Line 1"), Some(0));
+    ///
+    /// graph.print().unwrap();
+    /// assert_eq!(graph.line_age("Line 1"), Some(0));
+    ///
+    /// graph.print().unwrap();
+    /// assert_eq!(graph.line_age("Line 1"), Some(1));
+    /// assert_eq!(graph.line_age("Line 2"), None);
+    /// ```
+    pub fn line_age<T>(&self, identifier: T) -> Option<usize>
+    where
+        T: fmt::Display,
+    {
+        self.find_line(&identifier.to_string())
+            .map(|line| line.ticks_since_data)
+    }
+
+    /// Returns true if the line did not receive any value for more than
+    /// `threshold` prints. Lines which do not exist are never stale.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new().with_fallback_size(80);
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.print_n_times(3).unwrap();
+    ///
+    /// assert!(graph.line_is_stale("Line 1", 1));
+    /// assert!(!graph.line_is_stale("Line 1", 2));
+    /// ```
+    pub fn line_is_stale<T>(&self, identifier: T, threshold: usize) -> bool
+    where
+        T: fmt::Display,
+    {
+        self.line_age(identifier).is_some_and(|age| age > threshold)
+    }
+
+    /// Returns all values of the line or `None` if it does not exist. The
+    /// values can be accessed via indexing, too, which panics if the line
+    /// does not exist.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    ///
+    /// assert_eq!(graph.line_values("Line 1"), Some(&[1, 2][..]));
+    /// assert_eq!(graph.line_values("Line 2"), None);
+    /// assert_eq!(&graph["Line 1"], &[1, 2]);
+    /// ```
+    pub fn line_values<T>(&self, identifier: T) -> Option<&[V]>
+    where
+        T: fmt::Display,
+    {
+        self.find_line(&identifier.to_string())
+            .map(|line| line.values.as_slice())
+    }
+
+    /// Returns the amount of values stored for the line or `None` if it does
+    /// not exist
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    ///
+    /// assert_eq!(graph.line_value_count("Line 1"), Some(2));
+    /// assert_eq!(graph.line_value_count("Line 2"), None);
+    /// ```
+    pub fn line_value_count<T>(&self, identifier: T) -> Option<usize>
+    where
+        T: fmt::Display,
+    {
+        self.find_line(&identifier.to_string())
+            .map(|line| line.values.len())
+    }
+
+    /// Returns the values of the line which have been added within the last
+    /// `duration` or `None` if the line does not exist. Only values added
+    /// while time series or timestamps are enabled are taken into account.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    /// use std::time::Duration;
+    ///
+    /// let mut graph = Graph::new().with_time_series();
+    /// graph.add("Line 1", 1).unwrap();
+    ///
+    /// let values = graph.time_windowed_values("Line 1", Duration::from_secs(1));
+    /// assert_eq!(values, Some(&[1][..]));
+    /// ```
+    pub fn time_windowed_values<T>(
+        &self,
+        identifier: T,
+        duration: Duration,
+    ) -> Option<&[V]>
+    where
+        T: fmt::Display,
+    {
+        self.find_line(&identifier.to_string())
+            .map(|line| line.windowed_values(duration))
+    }
+
+    /// Returns a snapshot of the names and values of all active lines
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    ///
+    /// assert_eq!(graph.snapshot().values("Line 1"), Some(&[1][..]));
+    /// ```
+    pub fn snapshot(&self) -> Snapshot<V> {
+        Snapshot {
+            lines: self
+                .columns
+                .iter()
+                .filter_map(|c| match *c {
+                    Column::Used(ref line)
+                        if !self.lines_to_be_removed.contains(&line.name) =>
+                    {
+                        Some((line.name.clone(), line.values.clone()))
+                    }
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the lines which have been added, removed or got new values
+    /// between the older snapshot `a` and the newer snapshot `b`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// let a = graph.snapshot();
+    /// graph.add("Line 1", 2).unwrap();
+    /// graph.add("Line 2", 3).unwrap();
+    /// let b = graph.snapshot();
+    ///
+    /// let diff = Graph::diff_snapshots(&a, &b);
+    /// assert_eq!(diff.added_lines, vec!["Line 2"]);
+    /// assert_eq!(diff.changed_lines, vec![("Line 1".to_owned(), vec![2])]);
+    /// ```
+    pub fn diff_snapshots(a: &Snapshot<V>, b: &Snapshot<V>) -> SnapshotDiff<V> {
+        let mut diff = SnapshotDiff {
+            added_lines: vec![],
+            removed_lines: vec![],
+            changed_lines: vec![],
+        };
+        for (name, values) in &b.lines {
+            match a.values(name) {
+                Some(old) if values.len() > old.len() => diff
+                    .changed_lines
+                    .push((name.clone(), values[old.len()..].to_vec())),
+                Some(_) => {}
+                None => diff.added_lines.push(name.clone()),
+            }
+        }
+        diff.removed_lines = a
+            .lines
+            .iter()
+            .filter(|(name, _)| b.values(name).is_none())
+            .map(|(name, _)| name.clone())
+            .collect();
+        diff
+    }
+
+    /// Returns the amount of values stored for all active lines, which
+    /// excludes the ones about to be removed
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    /// graph.add("Line 2", 3).unwrap();
+    /// assert_eq!(graph.total_value_count(), 3);
+    ///
+    /// graph.remove("Line 1").unwrap();
+    /// assert_eq!(graph.total_value_count(), 1);
+    /// ```
+    pub fn total_value_count(&self) -> usize {
+        self.columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line)
+                    if !self.lines_to_be_removed.contains(&line.name) =>
+                {
+                    Some(line.values.len())
+                }
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Returns the maximum amount of lines which have been part of the graph
+    /// at the same time
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    /// graph.remove("Line 1").unwrap();
+    ///
+    /// assert_eq!(graph.peak_line_count(), 2);
+    /// ```
+    pub fn peak_line_count(&self) -> usize {
+        self.peak_line_count
+    }
+
+    /// Move the visible columns `n` columns to the left if the overflow
+    /// policy is `OverflowPolicy::Scroll`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, OverflowPolicy};
+    ///
+    /// let mut graph: Graph<u8> =
+    ///     Graph::new().with_overflow_policy(OverflowPolicy::Scroll);
+    /// graph.scroll_left(1);
+    /// ```
+    pub fn scroll_left(&mut self, n: usize) {
+        self.viewport_offset = self.viewport_offset.saturating_sub(n);
+    }
+
+    /// Move the visible columns `n` columns to the right if the overflow
+    /// policy is `OverflowPolicy::Scroll`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, OverflowPolicy};
+    ///
+    /// let mut graph: Graph<u8> =
+    ///     Graph::new().with_overflow_policy(OverflowPolicy::Scroll);
+    /// graph.scroll_right(1);
+    /// ```
+    pub fn scroll_right(&mut self, n: usize) {
+        let max_offset = self.columns.len().saturating_sub(1);
+        self.viewport_offset =
+            self.viewport_offset.saturating_add(n).min(max_offset);
+    }
+
+    /// Returns the start and (exclusive) end index of the columns which will
+    /// be visible on the next print. A terminal width of 80 columns is
+    /// assumed if the width can not be determined.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    /// graph.add("Line 2", 0).unwrap();
+    ///
+    /// assert_eq!(graph.visible_range(), (0, 2));
+    /// ```
+    pub fn visible_range(&self) -> (usize, usize) {
+        let width = self.current_width().unwrap_or(80);
+        let row_info_width = self.row_info().map(|i| i.width()).unwrap_or(0);
+        let offset = self.scroll_offset();
+        let mut cursor = (row_info_width + self.prefix_length()) as u16;
+        if offset > 0 {
+            cursor += 1;
+        }
+        let visible =
+            self.fitting_columns(cursor, Self::end_cursor(width), offset);
+        (offset, offset + visible)
+    }
+
+    /// Prints the graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    ///
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert!(graph.add("Line 2", 0).is_ok());
+    ///
+    /// graph.print();
+    /// ```
+    pub fn print(&mut self) -> RainResult<()> {
+        let result = self.print_row(false);
+        self.handle_error(result, ())
+    }
+
+    /// Prints the graph once without any colors or other escape sequences,
+    /// for example to write it into a file
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// graph.print_raw();
+    /// ```
+    pub fn print_raw(&mut self) -> RainResult<()> {
+        let result = self.print_row(true);
+        self.handle_error(result, ())
+    }
+
+    /// Prints the graph and logs a warning instead of returning an error
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// graph.try_print();
+    /// ```
+    pub fn try_print(&mut self) {
+        if let Err(e) = self.print() {
+            warn!("Unable to print graph: {}", e);
+        }
+    }
+
+    /// Prints a single row containing the minimum, maximum and mean of the
+    /// latest values of all active lines, aligned with the columns of the
+    /// graph
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 2).unwrap();
+    /// graph.add("Line 2", 4).unwrap();
+    ///
+    /// graph.print_summary();
+    /// ```
+    pub fn print_summary(&mut self) -> RainResult<()> {
+        let result = self.print_summary_row();
+        self.handle_error(result, ())
+    }
+
+    /// Prints the graph `n` times, regardless if new data arrived in the
+    /// meantime
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// graph.print_n_times(3);
+    /// ```
+    pub fn print_n_times(&mut self, n: usize) -> RainResult<()> {
+        for _ in 0..n {
+            self.print()?;
+        }
+        Ok(())
+    }
+
+    /// Keep all printed rows in memory instead of writing them to the output
+    /// until `flush_buffer` is called
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// graph.begin_buffer();
+    /// graph.print_n_times(3);
+    /// graph.flush_buffer();
+    /// ```
+    pub fn begin_buffer(&mut self) {
+        if self.buffer.is_none() {
+            self.buffer = Some(vec![]);
+        }
+    }
+
+    /// Write all buffered rows to the output at once and stop buffering
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new();
+    /// assert!(graph.flush_buffer().is_ok());
+    /// ```
+    pub fn flush_buffer(&mut self) -> RainResult<()> {
+        if let Some(buffer) = self.buffer.take() {
+            if !buffer.is_empty() {
+                let mut output = buffer.join("\n");
+                output.push('\n');
+                self.output.write_all(output.as_bytes())?;
+                self.output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Renders the graph into rows without printing them or changing the
+    /// state of the graph. A subsequent `print` will output the same rows.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::with_prefix_length(10).with_fallback_size(80);
+    /// graph.add("Line 1", 0).unwrap();
+    ///
+    /// let rows = graph.render().unwrap();
+    /// assert_eq!(rows.len(), 1);
+    /// assert!(rows[0].contains("Line 1"));
+    /// ```
+    pub fn render(&self) -> RainResult<Vec<String>> {
+        let width = self.current_width()?;
+        let (rows, _) = self.render_rows(width, self.prefix_length())?;
+        Ok(rows)
+    }
+
+    /// Prints a single row of the graph
+    fn print_row(&mut self, raw: bool) -> RainResult<()> {
+        // Wait until the rate limit allows printing again
+        if let Some(remaining) = self.rate_limit_remaining() {
+            debug!("Rate limit reached, waiting {:?}", remaining);
+            thread::sleep(remaining);
+        }
+        let render_start = Instant::now();
+
+        let width = self.terminal_width()?;
+
+        let (rows, visible) = self.render_rows(width, self.prefix_length())?;
+        for output in rows {
+            let output = if raw { strip_ansi(&output) } else { output };
+            match self.buffer {
+                Some(ref mut buffer) => buffer.push(output),
+                None => writeln!(self.output, "{}", output)?,
+            }
+        }
+
+        // Start all visible lines and free the ones which have been removed
+        for (index, column) in self.columns.iter_mut().enumerate() {
+            if let Column::Used(ref mut line) = *column {
+                if line.got_data {
+                    line.ticks_since_data = 0;
+                } else {
+                    line.ticks_since_data += 1;
+                }
+                line.got_data = false;
+            }
+            let free_column = match *column {
+                Column::Used(ref mut line) if visible.contains(&index) => {
+                    let removed = line.started
+                        && self.lines_to_be_removed.contains(&line.name);
+                    line.started = true;
+                    removed
+                }
+                Column::Used(ref line) => {
+                    self.lines_to_be_removed.contains(&line.name)
+                }
+                Column::Free => false,
+            };
+            if free_column {
+                *column = Column::Free;
+            }
+        }
+
+        // Close the gaps of the freed columns if needed
+        if self.config.compact_removed {
+            self.columns.retain(|c| *c != Column::Free);
+        }
+
+        // Move the visible columns further if scrolling
+        if self.config.overflow_policy == OverflowPolicy::Scroll {
+            self.viewport_offset = if visible.end < self.columns.len() {
+                visible.start + 1
+            } else {
+                0
+            };
+        }
+
+        // Cleanup lines to be removed
+        self.lines_to_be_removed.clear();
+        self.print_count += 1;
+        self.last_print = Some(Instant::now());
+        let render_time = render_start.elapsed();
+        self.last_render_time = Some(render_time);
+        if let Some(ref mut metrics) = self.metrics {
+            metrics.update(render_time);
+        }
+        if let Some(ref hook) = self.print_hook {
+            hook(self);
+        }
+        Ok(())
+    }
+
+    /// Prints the summary of the latest values of all active lines
+    fn print_summary_row(&mut self) -> RainResult<()> {
+        let values: Vec<V> = self
+            .active_lines()
+            .iter()
+            .filter_map(|name| self.find_line(name))
+            .filter_map(|line| line.values.last().cloned())
+            .collect();
+        let statistics = match (values.iter().min(), values.iter().max()) {
+            (Some(min), Some(max)) => {
+                let sum: f64 = values.iter().cloned().map(f64::from).sum();
+                format!(
+                    "min={} max={} mean={}",
+                    self.format_value(min.clone()),
+                    self.format_value(max.clone()),
+                    self.config.value_format.format(sum / values.len() as f64)
+                )
+            }
+            _ => "min=- max=- mean=-".to_owned(),
+        };
+        let indent = self.row_info()?.width() + self.prefix_length();
+        let output = format!(
+            "{}{} active={}",
+            " ".repeat(indent),
+            statistics,
+            values.len()
+        );
+        match self.buffer {
+            Some(ref mut buffer) => buffer.push(output),
+            None => writeln!(self.output, "{}", output)?,
+        }
+        Ok(())
+    }
+
+    /// Renders the next rows of the graph including the title and column
+    /// labels if needed and returns them together with the range of the visible columns
+    fn render_rows(
+        &self,
+        width: u16,
+        prefix_len: usize,
+    ) -> RainResult<(Vec<String>, ops::Range<usize>)> {
+        let row = self.render_row(width, prefix_len)?;
+        let mut rows = vec![];
+        if self.print_count == 0 {
+            if let Some(ref title) = self.config.title {
+                rows.push(self.render_title(title, width));
+            }
+            if self.config.column_labels {
+                rows.push(row.labels);
+            }
+        }
+        rows.push(row.content);
+        Ok((rows, row.visible))
+    }
+
+    /// Renders the title centered within the terminal width
+    fn render_title(&self, title: &str, width: u16) -> String {
+        let text = format!(" {} ", title);
+        let text = truncate(&text, usize::from(width));
+        let padding = usize::from(width) - text.width();
+        let fill = |count: usize| {
+            format!(
+                "{}{}{}",
+                backend::fg_fill(),
+                self.config.fill_char.to_string().repeat(count),
+                backend::fg_reset()
+            )
+        };
+        format!(
+            "{}{}{}",
+            fill(padding / 2),
+            text,
+            fill(padding - padding / 2)
+        )
+    }
+
+    /// Renders a single row of the graph
+    fn render_row(
+        &self,
+        width: u16,
+        prefix_len: usize,
+    ) -> RainResult<RenderedRow> {
+        // Prints the fillchar to the terminal
+        let fill_char = self.config.fill_char;
+        let fillchar = || {
+            format!(
+                "{}{}{}",
+                backend::fg_fill(),
+                fill_char,
+                backend::fg_reset()
+            )
+        };
+
+        // Do the actual printing per column
+        let start_ch = "┬";
+        let line_chr = "│";
+        let nodata_c = "╎";
+        let axis_chr = "┼";
+        let end_char = "┴";
+        let col_width = self.column_width();
+
+        // Get the leading row information like timestamps if needed
+        let row_info = self.row_info()?;
+
+        // Keep space for at least a single column and the overflow sign
+        let required = row_info.width() + prefix_len + col_width as usize + 1;
+        if usize::from(width) < required {
+            bail!(
+                ErrorType::TerminalTooNarrow { width, required },
+                "Terminal width {} is less than the required {}",
+                width,
+                required
+            );
+        }
+
+        let mut cursor = (row_info.width() + prefix_len) as u16;
+        let end_cursor = Self::end_cursor(width);
+
+        // A string representation for a row to be printed
+        struct Row {
+            content: String,
+            prefix: Option<String>,
+        }
+        let mut row = Row {
+            content: String::with_capacity(width as usize),
+            prefix: None,
+        };
+
+        // Get the current minimum and maximum values from all lines
+        let (min, max) = self.value_bounds();
+        let warmup = self.print_count < self.config.auto_scale_delay;
+        let (color_min, color_max) = if warmup {
+            (0f64, 0f64)
+        } else if self.config.derivative {
+            self.derivative_bounds()
+        } else {
+            (f64::from(min.clone()), f64::from(max.clone()))
+        };
+        let (block_min, block_max) = if warmup {
+            (min.clone(), min.clone())
+        } else {
+            (min.clone(), max.clone())
+        };
+        let soft_scale = self.config.soft_scale;
+
+        // Determine the columns which fit into the terminal
+        let offset = self.scroll_offset();
+        if offset > 0 {
+            row.content.push(self.config.overflow_char);
+            cursor += 1;
+        }
+        let visible = self.fitting_columns(cursor, end_cursor, offset);
+        let total = self.columns.len();
+        if self.config.overflow_policy == OverflowPolicy::Error
+            && visible < total
+        {
+            bail!(
+                ErrorType::ColumnsOverflow { visible, total },
+                "Only {} of {} columns fit into the terminal",
+                visible,
+                total
+            );
+        }
+
+        // Gather all columns together
+        let mut labels = String::new();
+        let mut used_columns = 0;
+        let mut previous_group = None;
+        let mut previous_used = false;
+        for column in self.columns.iter().skip(offset).take(visible) {
+            match *column {
+                Column::Used(ref line) => {
+                    // Separate the line from the previous one if the group
+                    // changes
+                    let group = Group::find(&self.config.groups, &line.name);
+                    if used_columns > 0 && group != previous_group {
+                        row.content += &fillchar();
+                        cursor += 1;
+                    }
+                    previous_group = group;
+
+                    // Keep a gap to the previous line
+                    if previous_used {
+                        for _ in 0..self.config.column_gap {
+                            row.content += &fillchar();
+                        }
+                        row.content += &self.config.line_separator;
+                        cursor += (self.config.column_gap
+                            + self.config.line_separator.width())
+                            as u16;
+                    }
+                    previous_used = true;
+
+                    // Get a row prefix format and keep three characters left
+                    let name_width = prefix_len.saturating_sub(3);
+                    let label = Group::label(&self.config.groups, &line.name);
+                    let name = truncate(&label, name_width);
+                    let padding = name_width - name.width();
+                    let alignment =
+                        line.alignment.unwrap_or(self.config.label_alignment);
+                    let left_padding = match alignment {
+                        LabelAlignment::Left => 0,
+                        LabelAlignment::Center => padding / 2,
+                        LabelAlignment::Right => padding,
+                    };
+                    let mut row_prefix = format!(
+                        "{}{}{}",
+                        " ".repeat(left_padding),
+                        name,
+                        " ".repeat(padding - left_padding)
+                    );
+
+                    // Get the character to be printed
+                    let values = self.shown_values(line);
+                    let c = if line.started {
+                        // Check if the line is done an can be used later on
+                        if self.lines_to_be_removed.contains(&line.name) {
+                            row_prefix += &self.config.end_marker;
+                            row.prefix = Some(row_prefix);
+                            end_char
+                        } else if self.axis.is_some()
+                            && self.axis.as_ref() == values.last()
+                        {
+                            axis_chr
+                        } else if line.got_data {
+                            line_chr
+                        } else {
+                            nodata_c
+                        }
+                    } else {
+                        row_prefix += &self.config.start_marker;
+                        row.prefix = Some(row_prefix);
+                        start_ch
+                    };
+
+                    // Get the rgb value for the character
+                    let value = values.last().cloned().unwrap_or_default();
+                    let color_value = if self.config.derivative {
+                        Self::derivative(values)
+                    } else {
+                        f64::from(value.clone())
+                    };
+                    let (color_min, color_max) =
+                        if self.config.per_line_scale && !warmup {
+                            self.line_color_bounds(line)
+                        } else {
+                            (color_min, color_max)
+                        };
+                    let group_color =
+                        Group::color(&self.config.groups, &line.name);
+                    let (r, g, b) = group_color.unwrap_or_else(|| {
+                        Self::rgb(soft_scale, color_min, color_max, color_value)
+                    });
+                    let color = match self.config.color_mode {
+                        ColorMode::TrueColor => backend::fg_rgb(r, g, b),
+                        ColorMode::Ansi256 => {
+                            backend::fg_ansi(Self::ansi_256(r, g, b))
+                        }
+                        ColorMode::Ansi8 => {
+                            backend::fg_basic(match group_color {
+                                Some((r, g, b)) => Self::ansi_8(r, g, b),
+                                None => Self::ansi_8_gradient(
+                                    color_min,
+                                    color_max,
+                                    color_value,
+                                ),
+                            })
+                        }
+                    };
+
+                    // Keep the position of the line for the column labels
+                    let padding =
+                        usize::from(cursor).saturating_sub(labels.width());
+                    labels += &" ".repeat(padding);
+                    labels += truncate(&line.name, usize::from(col_width));
+
+                    // Encode the values within the character if needed
+                    let c = if self.config.braille {
+                        Self::braille(values).to_string()
+                    } else if self.config.sparkline {
+                        Self::block(
+                            block_min.clone(),
+                            block_max.clone(),
+                            value.clone(),
+                        )
+                        .to_owned()
+                    } else {
+                        c.to_owned()
+                    };
+
+                    // Bold highlighted lines and, if enabled, the latest ones
+                    let bold = line.highlighted
+                        || (self.config.bold_latest && line.got_data);
+                    if bold {
+                        row.content += &format!(
+                            "{}{}{}{}",
+                            color,
+                            backend::bold(),
+                            c,
+                            backend::reset()
+                        );
+                    } else {
+                        row.content +=
+                            &format!("{}{}{}", color, c, backend::fg_reset());
+                    }
+                    for _ in 1..col_width {
+                        row.content += &fillchar();
+                    }
+
+                    // Separate the groups of lines
+                    used_columns += 1;
+                    if self.config.group_separator > 0
+                        && used_columns % self.config.group_separator == 0
+                    {
+                        row.content += &fillchar();
+                        cursor += 1;
+                    }
+                }
+                Column::Free => {
+                    for _ in 0..col_width {
+                        row.content += &fillchar();
+                    }
+                    previous_used = false;
+                }
+            }
+
+            cursor += col_width;
+        }
+
+        // Indicate the columns which do not fit
+        if offset + visible < total {
+            row.content.push(self.config.overflow_char);
+            cursor += 1;
+        }
+
+        // Fill rest of the screen
+        for _ in cursor..width {
+            row.content += &fillchar();
+        }
+
+        // Build the row including the prefix if set
+        let prefix_string = match row.prefix {
+            Some(prefix) if self.config.show_prefix => prefix,
+            _ => " ".repeat(prefix_len),
+        };
+        Ok(RenderedRow {
+            content: format!("{}{}{}", row_info, prefix_string, row.content),
+            labels,
+            visible: offset..offset + visible,
+        })
+    }
+
+    /// Print only if new data is available. Returns an indicator if somethings
+    /// was printed or not.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    ///
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert!(graph.add("Line 2", 0).is_ok());
+    ///
+    /// graph.print_if_new_data();
+    /// graph.print_if_new_data();
+    /// ```
+    pub fn print_if_new_data(&mut self) -> RainResult<bool> {
+        if self.rate_limit_remaining().is_some() {
+            return Ok(false);
+        }
+
+        // Wait until the data settled down
+        if let (Some(debounce), Some(last_add)) =
+            (self.config.debounce, self.last_add)
+        {
+            if last_add.elapsed() < debounce {
+                return Ok(false);
+            }
+        }
+
+        if !self.lines_to_be_removed.is_empty()
             || self
                 .columns
                 .iter()
-                .filter(|c| match **c {
-                    Column::Used(ref line) if line.got_data => true,
-                    _ => false,
-                })
+                .filter(
+                    |c| matches!(**c, Column::Used(ref line) if line.got_data),
+                )
                 .count()
                 > 0
         {
-            self.print()?;
-            Ok(true)
+            let result = self.print_row(false).map(|_| true);
+            self.handle_error(result, false)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Returns the rendering performance metrics if enabled
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new().with_metrics_enabled();
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert_eq!(graph.metrics().unwrap().render_count, 0);
+    /// ```
+    pub fn metrics(&self) -> Option<&GraphMetrics> {
+        self.metrics.as_ref()
+    }
+
+    /// Returns the rendering duration of the last print
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> = Graph::new();
+    /// assert!(graph.last_render_duration().is_none());
+    /// ```
+    pub fn last_render_duration(&self) -> Option<Duration> {
+        self.last_render_time
+    }
+
+    /// Returns the average rendering duration of all prints if the metrics
+    /// are enabled
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> = Graph::new().with_metrics_enabled();
+    /// assert!(graph.average_render_duration().is_none());
+    /// ```
+    pub fn average_render_duration(&self) -> Option<Duration> {
+        self.metrics
+            .as_ref()
+            .filter(|metrics| metrics.render_count > 0)
+            .map(|metrics| {
+                let average =
+                    metrics.total_render_ns / u128::from(metrics.render_count);
+                Duration::from_nanos(u64::try_from(average).unwrap_or(u64::MAX))
+            })
+    }
+
+    /// Reset all rendering performance metrics to zero
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new().with_metrics_enabled();
+    /// graph.reset_metrics();
+    /// ```
+    pub fn reset_metrics(&mut self) {
+        if let Some(ref mut metrics) = self.metrics {
+            *metrics = GraphMetrics::default();
+        }
+    }
+
+    /// Invalidate the cached terminal size, which will be determined again on
+    /// the next print
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new().with_no_resize();
+    /// graph.force_resize_check();
+    /// ```
+    pub fn force_resize_check(&mut self) {
+        self.cached_width = None;
+    }
+
+    /// Returns true if the graph output target is an interactive terminal
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let graph: Graph<u8> = Graph::new();
+    /// if !graph.is_tty() {
+    ///     println!("Not a terminal");
+    /// }
+    /// ```
+    pub fn is_tty(&self) -> bool {
+        match self.output {
+            OutputTarget::Stdout => io::stdout().is_terminal(),
+            OutputTarget::Stderr => io::stderr().is_terminal(),
+            OutputTarget::Custom(_) => false,
+        }
+    }
+
+    /// Export the value history of all lines as SVG 1.1 document, where every
+    /// value is represented by a colored rectangle of the provided height
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert!(graph.add("Line 1", 5).is_ok());
+    ///
+    /// let mut svg = vec![];
+    /// assert!(graph.export_svg(10, &mut svg).is_ok());
+    /// ```
+    pub fn export_svg<W>(&self, line_height: u32, w: &mut W) -> RainResult<()>
+    where
+        W: Write,
+    {
+        let (min, max) = self.value_bounds();
+        let rows = self
+            .columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) => Some(line.values.len()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        let column_width = u64::from(line_height);
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            w,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="{}" height="{}">"#,
+            self.columns.len() as u64 * column_width,
+            rows as u64 * u64::from(line_height),
+        )?;
+        for (x, column) in self.columns.iter().enumerate() {
+            if let Column::Used(ref line) = *column {
+                writeln!(w, "<g><title>{}</title>", escape_xml(&line.name))?;
+                for (y, value) in line.values.iter().enumerate() {
+                    let (r, g, b) = Self::rgb(
+                        self.config.soft_scale,
+                        f64::from(min.clone()),
+                        f64::from(max.clone()),
+                        f64::from(value.clone()),
+                    );
+                    writeln!(
+                        w,
+                        r#"<rect x="{}" y="{}" width="{}" height="{}" fill="rgb({},{},{})"/>"#,
+                        x as u64 * column_width,
+                        y as u64 * u64::from(line_height),
+                        column_width,
+                        line_height,
+                        r,
+                        g,
+                        b
+                    )?;
+                }
+                writeln!(w, "</g>")?;
+            }
+        }
+        writeln!(w, "</svg>")?;
+        Ok(())
+    }
+
+    /// Export the value history of all lines as CSV, where the first row
+    /// contains the names of the lines and every further row the values of a
+    /// single tick
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert!(graph.add("Line 1", 5).is_ok());
+    ///
+    /// let mut csv = vec![];
+    /// assert!(graph.export_csv(&mut csv).is_ok());
+    /// assert_eq!(csv, b"tick,Line 1\n0,0\n1,5\n");
+    /// ```
+    pub fn export_csv<W>(&self, w: &mut W) -> RainResult<()>
+    where
+        W: Write,
+        V: fmt::Display,
+    {
+        self.write_csv(w, false)
+    }
+
+    /// Export the value history of all lines as CSV like `export_csv`,
+    /// including the milliseconds since the first value of the graph for
+    /// every tick. Only values added while timestamps are enabled carry a
+    /// time, the column is empty for all other ticks.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new().with_timestamp_prefix();
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    ///
+    /// let mut csv = vec![];
+    /// assert!(graph.export_csv_with_timestamps(&mut csv).is_ok());
+    /// assert_eq!(csv, b"tick,elapsed_ms,Line 1\n0,0,0\n");
+    /// ```
+    pub fn export_csv_with_timestamps<W>(&self, w: &mut W) -> RainResult<()>
+    where
+        W: Write,
+        V: fmt::Display,
+    {
+        self.write_csv(w, true)
+    }
+
+    /// Writes the value history of all lines as CSV
+    fn write_csv<W>(&self, w: &mut W, timestamps: bool) -> RainResult<()>
+    where
+        W: Write,
+        V: fmt::Display,
+    {
+        let lines: Vec<&Line<V>> = self
+            .columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) => Some(line),
+                _ => None,
+            })
+            .collect();
+        let rows = lines
+            .iter()
+            .map(|line| line.values.len())
+            .max()
+            .unwrap_or_default();
+
+        let start = lines
+            .iter()
+            .filter_map(|line| line.timestamps.first())
+            .min();
+
+        write!(w, "tick")?;
+        if timestamps {
+            write!(w, ",elapsed_ms")?;
+        }
+        for line in &lines {
+            write!(w, ",{}", escape_csv(&line.name))?;
+        }
+        writeln!(w)?;
+        for tick in 0..rows {
+            write!(w, "{}", tick)?;
+            if timestamps {
+                let time =
+                    lines.iter().filter_map(|line| line.timestamp(tick)).min();
+                match (time, start) {
+                    (Some(time), Some(start)) => write!(
+                        w,
+                        ",{}",
+                        time.duration_since(*start).as_millis()
+                    )?,
+                    _ => write!(w, ",")?,
+                }
+            }
+            for line in &lines {
+                match line.values.get(tick) {
+                    Some(value) => write!(w, ",{}", value)?,
+                    None => write!(w, ",")?,
+                }
+            }
+            writeln!(w)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the amount of printed rows since creation or the last call to
+    /// `reset_print_count`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert_eq!(graph.print_count(), 0);
+    /// ```
+    pub fn print_count(&self) -> usize {
+        self.print_count
+    }
+
+    /// Reset the amount of printed rows to zero
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph: Graph<u8> = Graph::new();
+    /// graph.reset_print_count();
+    /// assert_eq!(graph.print_count(), 0);
+    /// ```
+    pub fn reset_print_count(&mut self) {
+        self.print_count = 0;
+    }
+
+    /// Returns the column index of the line or an error if it does not exist
+    fn existing_column_index<T>(&self, identifier: T) -> RainResult<usize>
+    where
+        T: fmt::Display,
+    {
+        match self.column_index(&identifier) {
+            Some(index) => Ok(index),
+            None => bail!(
+                ErrorType::LineDoesNotExist {
+                    name: identifier.to_string()
+                },
+                "Line does not exist and can not be moved"
+            ),
+        }
+    }
+
+    /// Removes the column, whereas a free column stays in place if the order
+    /// of the lines is locked
+    fn take_column(&mut self, index: usize) -> Column<V> {
+        if self.config.lock_order {
+            mem::replace(&mut self.columns[index], Column::Free)
+        } else {
+            self.columns.remove(index)
+        }
+    }
+
+    /// Returns the current time if timestamps or time series are enabled
+    fn timestamp(&self) -> Option<Instant> {
+        if self.config.timestamp_format.is_some() || self.config.time_series {
+            Some(Instant::now())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the amount of columns which are currently in use
+    fn used_columns(&self) -> usize {
+        self.columns
+            .iter()
+            .filter(|c| matches!(**c, Column::Used(_)))
+            .count()
+    }
+
+    /// Fails if the new lines would exceed the maximum amount of columns
+    fn check_max_columns(&self, new_lines: usize) -> RainResult<()> {
+        if let Some(limit) = self.config.max_columns {
+            let attempted = self.used_columns() + new_lines;
+            if attempted > limit {
+                bail!(
+                    ErrorType::MaxColumnsExceeded { limit, attempted },
+                    "Line can not be added because only {} columns are \
+                     allowed",
+                    limit
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the next free column and set the column as used
+    fn get_next_free_column(&mut self) -> &mut Column<V> {
+        self.peak_line_count =
+            self.peak_line_count.max(self.used_columns() + 1);
+
+        let free_column_count = self
+            .columns
+            .iter_mut()
+            .filter(|c| **c == Column::Free)
+            .count();
+
+        if free_column_count == 0 || self.config.lock_order {
+            self.columns.push(Column::Free);
+            self.columns.iter_mut().next_back().unwrap()
+        } else {
+            self.columns
+                .iter_mut()
+                .find(|c| **c == Column::Free)
+                .unwrap()
+        }
+    }
+
+    /// Passes the error of the result to the error handler if set and returns
+    /// the fallback value instead
+    fn handle_error<T>(
+        &self,
+        result: RainResult<T>,
+        fallback: T,
+    ) -> RainResult<T> {
+        match (result, &self.error_handler) {
+            (Err(e), Some(handler)) => {
+                handler(e);
+                Ok(fallback)
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Returns the current terminal width and caches it if resizing is
+    /// disabled
+    fn terminal_width(&mut self) -> RainResult<u16> {
+        let width = self.current_width()?;
+
+        // Keep the width for later usage if resizing is disabled
+        if self.config.no_resize {
+            self.cached_width = Some(width);
+        }
+        Ok(width)
+    }
+
+    /// Returns the cached or current terminal width or the fallback width if
+    /// set
+    fn current_width(&self) -> RainResult<u16> {
+        if let Some(width) = self.cached_width {
+            return Ok(width);
+        }
+
+        // The terminal does not matter if the output goes somewhere else
+        if let (false, Some(width)) =
+            (self.is_tty(), self.config.fallback_width)
+        {
+            return Ok(width);
+        }
+
+        match (backend::terminal_size(), self.config.fallback_width) {
+            (Ok((width, _)), _) => Ok(width),
+            (Err(e), Some(width)) => {
+                debug!("Using fallback width {}: {}", width, e);
+                Ok(width)
+            }
+            (Err(e), None) => Err(e.into()),
+        }
+    }
+
+    /// Returns the time to wait until the rate limit allows printing again
+    fn rate_limit_remaining(&self) -> Option<Duration> {
+        match (self.config.rate_limit, self.last_print) {
+            (Some(rate_limit), Some(last_print)) => rate_limit
+                .checked_sub(last_print.elapsed())
+                .filter(|remaining| !remaining.is_zero()),
+            _ => None,
+        }
+    }
+
+    /// Returns the prefix length used for rendering, which is computed from
+    /// the line names if the automatic prefix length is enabled
+    fn prefix_length(&self) -> usize {
+        if self.config.auto_prefix && self.config.show_prefix {
+            self.auto_prefix_length()
         } else {
-            Ok(false)
+            self.config.prefix_len
+        }
+    }
+
+    /// Returns the prefix length which fits the longest line name
+    fn auto_prefix_length(&self) -> usize {
+        let longest_name = self
+            .columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) => {
+                    Some(Group::label(&self.config.groups, &line.name).width())
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        longest_name
+            .max(self.config.min_prefix_len)
+            .min(self.config.max_prefix_len)
+            + 3
+    }
+
+    /// Set the highlight flag of an existing line
+    fn set_highlighted<T>(
+        &mut self,
+        identifier: T,
+        highlighted: bool,
+    ) -> RainResult<()>
+    where
+        T: fmt::Display,
+    {
+        let line_name = format!("{}", identifier);
+        match self.line_already_existing(&line_name) {
+            Some(line) => {
+                debug!(
+                    "Setting highlight of line '{}' to {}",
+                    line_name, highlighted
+                );
+                line.highlighted = highlighted;
+                Ok(())
+            }
+            None => bail!(
+                ErrorType::LineDoesNotExist { name: line_name },
+                "Line does not exist and can not be highlighted"
+            ),
+        }
+    }
+
+    // Returns a shared line reference if the name exist within all columns
+    fn find_line(&self, line_name: &str) -> Option<&Line<V>> {
+        self.columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) if line_name == line.name => Some(line),
+                _ => None,
+            })
+            .next()
+    }
+
+    // Returns a line if the name already exist within all columns
+    fn line_already_existing(
+        &mut self,
+        line_name: &str,
+    ) -> Option<&mut Line<V>> {
+        let line_string = line_name.to_owned();
+        self.columns
+            .iter_mut()
+            .filter_map(|c| match *c {
+                Column::Used(ref mut line) if line_string == line.name => {
+                    Some(line)
+                }
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the leading information of a row like timestamps
+    fn row_info(&self) -> RainResult<String> {
+        use std::fmt::Write;
+        let mut row_info = String::new();
+        if let Some(ref format) = self.config.timestamp_format {
+            write!(row_info, "{} ", Local::now().format(format))?;
+        }
+        if self.config.row_numbers {
+            write!(row_info, "[{:04}] ", self.print_count)?;
+        }
+        if self.config.tick_counter {
+            write!(row_info, "[{:06}] ", self.print_count)?;
+        }
+        Ok(row_info)
+    }
+
+    /// Returns the last usable cursor position of a row
+    fn end_cursor(width: u16) -> u16 {
+        match width % 2 {
+            0 => width.saturating_sub(2),
+            _ => width - 1,
+        }
+    }
+
+    /// Returns the width of a single column
+    fn column_width(&self) -> u16 {
+        if self.config.compact {
+            1
+        } else {
+            2
+        }
+    }
+
+    /// Returns the index of the first visible column
+    fn scroll_offset(&self) -> usize {
+        match self.config.overflow_policy {
+            OverflowPolicy::Scroll
+                if self.viewport_offset < self.columns.len() =>
+            {
+                self.viewport_offset
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the amount of columns starting at the offset which fit between
+    /// both cursor positions
+    fn fitting_columns(
+        &self,
+        mut cursor: u16,
+        end_cursor: u16,
+        offset: usize,
+    ) -> usize {
+        let mut used_columns = 0;
+        let mut previous_group = None;
+        let mut previous_used = false;
+        let mut count = 0;
+        for column in self.columns.iter().skip(offset) {
+            // Sum up the width of the column including all separators
+            let mut needed = usize::from(self.column_width());
+            let mut group = previous_group;
+            if let Column::Used(ref line) = *column {
+                group = Group::find(&self.config.groups, &line.name);
+                if used_columns > 0 && group != previous_group {
+                    needed += 1;
+                }
+                if previous_used {
+                    needed += self.config.column_gap
+                        + self.config.line_separator.width();
+                }
+                if self.config.group_separator > 0
+                    && (used_columns + 1) % self.config.group_separator == 0
+                {
+                    needed += 1;
+                }
+            }
+            if usize::from(end_cursor) < usize::from(cursor) + needed {
+                break;
+            }
+
+            if let Column::Used(_) = *column {
+                used_columns += 1;
+                previous_group = group;
+            }
+            previous_used = matches!(*column, Column::Used(_));
+            cursor += needed as u16;
+            count += 1;
+        }
+        count
+    }
+
+    /// Returns the values of the line which are part of the display
+    fn shown_values<'a>(&self, line: &'a Line<V>) -> &'a [V] {
+        match self.config.time_window {
+            Some(duration) => line.windowed_values(duration),
+            None => &line.values,
+        }
+    }
+
+    /// Returns the minimum and maximum value of all lines
+    fn value_bounds(&self) -> (V, V) {
+        // Returns the maximum or minimum value of all available values
+        macro_rules! get_value {
+            ($($p:ident)*) => (
+                $(self.columns.iter().filter_map(|c| {
+                    match *c {
+                        Column::Used(ref line) => {
+                            self.shown_values(line).iter().$p()
+                        }
+                        _ => None,
+                    }
+                }).$p().cloned().unwrap_or_default())*
+            )
+        }
+
+        (get_value!(min), get_value!(max))
+    }
+
+    /// Returns the braille character representing the latest four values
+    fn braille(values: &[V]) -> char {
+        // The dots of the left braille column from top to bottom
+        let dots = [0x01, 0x02, 0x04, 0x40];
+
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let pattern = match sorted.get(sorted.len() / 2) {
+            Some(median) => values
+                .iter()
+                .rev()
+                .take(dots.len())
+                .rev()
+                .zip(dots.iter())
+                .filter(|(value, _)| *value >= median)
+                .fold(0, |pattern, (_, dot)| pattern | dot),
+            None => 0,
+        };
+        char::from_u32(0x2800 + pattern).unwrap_or(' ')
+    }
+
+    /// Returns the block character representing the height of the value
+    fn block(minimum: V, maximum: V, value: V) -> &'static str {
+        let blocks = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+        if minimum == maximum {
+            return blocks[0];
+        }
+
+        let minimum = f64::from(minimum);
+        let maximum = f64::from(maximum);
+        let value = f64::from(value);
+        let ratio = (value - minimum) / (maximum - minimum);
+        let index = (ratio * (blocks.len() - 1) as f64).round() as usize;
+        blocks[index.min(blocks.len() - 1)]
+    }
+
+    /// Returns the difference between the latest two values
+    fn derivative(values: &[V]) -> f64 {
+        match values {
+            [.., previous, last] => {
+                f64::from(last.clone()) - f64::from(previous.clone())
+            }
+            _ => 0f64,
+        }
+    }
+
+    /// Returns the minimum and maximum difference between all consecutive
+    /// values of all lines
+    fn derivative_bounds(&self) -> (f64, f64) {
+        self.columns
+            .iter()
+            .filter_map(|c| match *c {
+                Column::Used(ref line) => {
+                    Some(self.shown_values(line).windows(2))
+                }
+                _ => None,
+            })
+            .flatten()
+            .map(|window| Self::derivative(window))
+            .fold(None, |bounds, d| match bounds {
+                Some((min, max)) => Some((d.min(min), d.max(max))),
+                None => Some((d, d)),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the minimum and maximum color value of a single line
+    fn line_color_bounds(&self, line: &Line<V>) -> (f64, f64) {
+        let values = self.shown_values(line);
+        let color_values: Vec<f64> = if self.config.derivative {
+            values.windows(2).map(Self::derivative).collect()
+        } else {
+            values.iter().cloned().map(f64::from).collect()
+        };
+        color_values
+            .into_iter()
+            .fold(None, |bounds, v| match bounds {
+                Some((min, max)) => Some((v.min(min), v.max(max))),
+                None => Some((v, v)),
+            })
+            .unwrap_or_default()
+    }
+
+    fn rgb(
+        soft_scale: u8,
+        minimum: f64,
+        maximum: f64,
+        value: f64,
+    ) -> (u8, u8, u8) {
+        if minimum >= maximum {
+            return (soft_scale, soft_scale, u8::MAX);
+        }
+
+        // Calculate a RGB value over the complete gradient
+        let ratio = 2f64 * (value - minimum) / (maximum - minimum);
+        let mut b = max(0, (255f64 * (1f64 - ratio)) as i64) as u8;
+        let mut r = max(0, (255f64 * (ratio - 1f64)) as i64) as u8;
+        let mut g = (255 - b - r) as u8;
+
+        // Lighten up the values
+        b = b.saturating_add(soft_scale);
+        r = r.saturating_add(soft_scale);
+        g = g.saturating_add(soft_scale);
+
+        (r, g, b)
+    }
+
+    /// Returns the basic ANSI color whose channels are enabled
+    fn ansi_8(r: u8, g: u8, b: u8) -> u8 {
+        let on = |c: u8| u8::from(c > 127);
+        on(r) | on(g) << 1 | on(b) << 2
+    }
+
+    /// Returns the basic ANSI color of the gradient bucket for the value
+    fn ansi_8_gradient(minimum: f64, maximum: f64, value: f64) -> u8 {
+        // Use blue like the RGB gradient does without a range
+        if minimum >= maximum {
+            return 4;
+        }
+        let ratio = (value - minimum) / (maximum - minimum);
+        (ratio * 8f64).clamp(0f64, 7f64) as u8
+    }
+
+    /// Returns the nearest color of the xterm 256 color cube
+    fn ansi_256(r: u8, g: u8, b: u8) -> u8 {
+        let level = |c: u8| (u16::from(c) * 5 + 127) / 255;
+        (16 + 36 * level(r) + 6 * level(g) + level(b)) as u8
+    }
+}
+
+impl<V> Default for Graph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: convert::From<V>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> ops::Index<&str> for Graph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: convert::From<V>,
+{
+    type Output = [V];
+
+    fn index(&self, identifier: &str) -> &[V] {
+        match self.line_values(identifier) {
+            Some(values) => values,
+            None => panic!("Line '{}' does not exist", identifier),
+        }
+    }
+}
+
+/// Graphs are equal if their lines contain the same values in the same column
+/// order, the same lines are about to be removed and the prefix lengths match
+impl<V> PartialEq for Graph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: convert::From<V>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.config.prefix_len == other.config.prefix_len
+            && self.lines_to_be_removed == other.lines_to_be_removed
+            && self.columns_iter().eq(other.columns_iter())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+/// The configuration of a `Graph`, which is independent from its data
+///
+/// Settings which depend on the value type, like the axis or the clamping
+/// bounds, as well as closures and the output target remain part of the
+/// graph itself.
+pub struct GraphConfig {
+    /// The width of the prefix including the marker characters
+    pub prefix_len: usize,
+
+    /// Render all lines which received data since the last print in bold
+    pub bold_latest: bool,
+
+    /// The amount of empty columns between groups of lines
+    pub group_separator: usize,
+
+    /// Adapt the prefix length to the longest line name
+    pub auto_prefix: bool,
+
+    /// The minimum width of the identifier if the prefix length is adapted
+    pub min_prefix_len: usize,
+
+    /// The maximum width of the identifier if the prefix length is adapted
+    pub max_prefix_len: usize,
+
+    /// The alignment of the identifier within the prefix column
+    pub label_alignment: LabelAlignment,
+
+    /// Print the identifier prefix at all
+    pub show_prefix: bool,
+
+    /// The `strftime` format of the timestamp prefix
+    pub timestamp_format: Option<String>,
+
+    /// Print an incrementing row counter in front of every row
+    pub tick_counter: bool,
+
+    /// Print the four digit row number in front of every row
+    pub row_numbers: bool,
+
+    /// The minimum duration between two prints
+    pub rate_limit: Option<Duration>,
+
+    /// The duration without new data before a print is done
+    pub debounce: Option<Duration>,
+
+    /// The terminal width to be used if it can not be determined
+    pub fallback_width: Option<u16>,
+
+    /// Determine the terminal width only once
+    pub no_resize: bool,
+
+    /// The maximum amount of used columns
+    pub max_columns: Option<usize>,
+
+    /// Render the values as block characters
+    pub sparkline: bool,
+
+    /// Render the values as braille characters
+    pub braille: bool,
+
+    /// Color the values by their change rather than their magnitude
+    pub derivative: bool,
+
+    /// Fail on values which had to be clamped
+    pub strict_clamping: bool,
+
+    /// The amount every color channel gets lightened up
+    pub soft_scale: u8,
+
+    /// The behavior if the columns do not fit into the terminal
+    pub overflow_policy: OverflowPolicy,
+
+    /// The amount of additional fill characters between adjacent lines
+    pub column_gap: usize,
+
+    /// The text between adjacent lines
+    pub line_separator: String,
+
+    /// The colors the terminal is able to display
+    pub color_mode: ColorMode,
+
+    /// The character which indicates columns which do not fit into the
+    /// terminal
+    pub overflow_char: char,
+
+    /// The character which fills the space between the lines
+    pub fill_char: char,
+
+    /// The three columns wide marker behind the identifier of a new line
+    pub start_marker: String,
+
+    /// The three columns wide marker behind the identifier of a removed line
+    pub end_marker: String,
+
+    /// The title above the first row of the graph
+    pub title: Option<String>,
+
+    /// Print the names of the lines above the first row of the graph
+    pub column_labels: bool,
+
+    /// Use a single character per column instead of two
+    pub compact: bool,
+
+    /// Print the graph immediately whenever a line gets removed
+    pub print_on_remove: bool,
+
+    /// Print the graph immediately whenever a new line gets added
+    pub print_on_add: bool,
+
+    /// The amount of prints before the color scale adapts to the values
+    pub auto_scale_delay: usize,
+
+    /// Scale the colors of every line by its own values
+    pub per_line_scale: bool,
+
+    /// Never reuse the columns of removed lines
+    pub lock_order: bool,
+
+    /// Remove the columns of removed lines on print
+    pub compact_removed: bool,
+
+    /// Keep the time of every added value
+    pub time_series: bool,
+
+    /// Display only the values added within the duration
+    pub time_window: Option<Duration>,
+
+    /// The logical groups of lines
+    pub groups: Vec<Group>,
+
+    /// The format of the textual value representation
+    pub value_format: ValueFormat,
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        GraphConfig {
+            prefix_len: 11,
+            bold_latest: false,
+            group_separator: 0,
+            auto_prefix: false,
+            min_prefix_len: 0,
+            max_prefix_len: 32,
+            label_alignment: LabelAlignment::Right,
+            show_prefix: true,
+            timestamp_format: None,
+            tick_counter: false,
+            row_numbers: false,
+            rate_limit: None,
+            debounce: None,
+            fallback_width: None,
+            no_resize: false,
+            max_columns: None,
+            sparkline: false,
+            braille: false,
+            derivative: false,
+            strict_clamping: false,
+            soft_scale: 125,
+            overflow_policy: OverflowPolicy::Truncate,
+            column_gap: 0,
+            line_separator: String::new(),
+            color_mode: ColorMode::TrueColor,
+            overflow_char: '…',
+            fill_char: '┈',
+            start_marker: " → ".to_owned(),
+            end_marker: " ← ".to_owned(),
+            title: None,
+            column_labels: false,
+            compact: false,
+            print_on_remove: false,
+            print_on_add: false,
+            auto_scale_delay: 0,
+            per_line_scale: false,
+            lock_order: false,
+            compact_removed: false,
+            time_series: false,
+            time_window: None,
+            groups: vec![],
+            value_format: ValueFormat::default(),
         }
     }
+}
 
-    /// Get the next free column and set the column as used
-    fn get_next_free_column(&mut self) -> &mut Column<V> {
-        let free_column_count = self
-            .columns
-            .iter_mut()
-            .filter(|c| **c == Column::Free)
-            .count();
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// Rendering performance metrics of a `Graph`
+pub struct GraphMetrics {
+    /// The amount of rendered rows
+    pub render_count: u64,
 
-        if free_column_count == 0 {
-            self.columns.push(Column::Free);
-            self.columns.iter_mut().rev().next().unwrap()
+    /// The overall rendering time in nanoseconds
+    pub total_render_ns: u128,
+
+    /// The fastest rendering time in nanoseconds
+    pub min_render_ns: u64,
+
+    /// The slowest rendering time in nanoseconds
+    pub max_render_ns: u64,
+}
+
+impl GraphMetrics {
+    /// Adds a single rendering duration to the metrics
+    fn update(&mut self, duration: Duration) {
+        let nanos = duration.as_nanos();
+        let nanos_u64 = u64::try_from(nanos).unwrap_or(u64::MAX);
+        if self.render_count == 0 || nanos_u64 < self.min_render_ns {
+            self.min_render_ns = nanos_u64;
+        }
+        self.max_render_ns = self.max_render_ns.max(nanos_u64);
+        self.total_render_ns += nanos;
+        self.render_count += 1;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+/// The alignment of the identifier text within the prefix column
+pub enum LabelAlignment {
+    /// Pad the identifier on the right
+    Left,
+
+    /// Pad the identifier on both sides
+    Center,
+
+    /// Pad the identifier on the left
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+/// The colors which are used to print the graph
+pub enum ColorMode {
+    /// 24 bit RGB colors
+    TrueColor,
+
+    /// The xterm 256 color palette
+    Ansi256,
+
+    /// The 8 basic ANSI colors
+    Ansi8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+/// The behavior if the columns do not fit into the terminal
+pub enum OverflowPolicy {
+    /// Cut off the columns which do not fit and indicate it with the overflow
+    /// character
+    Truncate,
+
+    /// Shift the visible columns to the right on every print, starting over
+    /// once the last column has been shown
+    Scroll,
+
+    /// Fail to print
+    Error,
+}
+
+/// The target where the graph will be printed to
+pub enum OutputTarget {
+    /// The standard output of the process
+    Stdout,
+
+    /// The standard error of the process
+    Stderr,
+
+    /// Any custom writer
+    Custom(Box<dyn Write + Send>),
+}
+
+impl Write for OutputTarget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match *self {
+            OutputTarget::Stdout => io::stdout().write(buf),
+            OutputTarget::Stderr => io::stderr().write(buf),
+            OutputTarget::Custom(ref mut writer) => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            OutputTarget::Stdout => io::stdout().flush(),
+            OutputTarget::Stderr => io::stderr().flush(),
+            OutputTarget::Custom(ref mut writer) => writer.flush(),
+        }
+    }
+}
+
+/// Escape the special characters of a string for the usage within XML
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Addition which saturates at the numeric bounds instead of overflowing
+pub trait SaturatingAdd {
+    /// Returns the sum or the numeric bound if the sum would overflow
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! impl_saturating_add {
+    ($($t:ty)*) => (
+        $(impl SaturatingAdd for $t {
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+        })*
+    )
+}
+
+impl_saturating_add! {
+    u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize
+}
+
+/// Quote a CSV field if it contains special characters
+fn escape_csv(text: &str) -> String {
+    if text.contains([',', '"']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_owned()
+    }
+}
+
+/// Split a CSV row into its unquoted fields
+fn split_csv(row: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = row.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push(c);
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Remove all ANSI escape sequences from a string
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip everything until the final byte of the control sequence
+            chars.find(|c| c.is_ascii_alphabetic());
         } else {
-            self.columns
-                .iter_mut()
-                .find(|c| **c == Column::Free)
-                .unwrap()
+            result.push(c);
         }
     }
+    result
+}
 
-    // Returns a line if the name already exist within all columns
-    fn line_already_existing(
-        &mut self,
-        line_name: &str,
-    ) -> Option<&mut Line<V>> {
-        let line_string = line_name.to_owned();
-        self.columns
-            .iter_mut()
-            .filter_map(|c| match *c {
-                Column::Used(ref mut line) if line_string == line.name => {
-                    Some(line)
-                }
-                _ => None,
-            })
-            .next()
+/// Fails if the character is not exactly one terminal column wide
+fn check_char_width(c: char) -> RainResult<()> {
+    let width = c.width().unwrap_or_default();
+    if width != 1 {
+        bail!(
+            ErrorType::InvalidCharacterWidth {
+                character: c,
+                width
+            },
+            "Character '{}' is {} columns wide instead of one",
+            c.escape_default(),
+            width
+        );
     }
+    Ok(())
+}
 
-    fn rgb(minimum: V, maximum: V, value: V) -> (u8, u8, u8) {
-        // Lightens up the colors
-        let soft_scale = 125;
-        if minimum == maximum {
-            return (soft_scale, soft_scale, u8::MAX);
+/// Fails if the marker is not exactly three terminal columns wide
+fn check_marker_width(marker: &str) -> RainResult<()> {
+    let width = marker.width();
+    if width != 3 {
+        bail!(
+            ErrorType::InvalidMarkerWidth {
+                marker: marker.to_owned(),
+                width
+            },
+            "Marker '{}' is {} columns wide instead of three",
+            marker.escape_default(),
+            width
+        );
+    }
+    Ok(())
+}
+
+/// Truncate a string to fit into the provided amount of terminal columns
+fn truncate(text: &str, width: usize) -> &str {
+    let mut used_width = 0;
+    for (index, c) in text.char_indices() {
+        used_width += c.width().unwrap_or_default();
+        if used_width > width {
+            return &text[..index];
         }
+    }
+    text
+}
 
-        // Calculate a RGB value over the complete gradient
-        let minimum = f64::from(minimum);
-        let maximum = f64::from(maximum);
-        let value = f64::from(value);
-        let ratio = 2f64 * (value - minimum) / (maximum - minimum);
-        let mut b = max(0, (255f64 * (1f64 - ratio)) as i64) as u8;
-        let mut r = max(0, (255f64 * (ratio - 1f64)) as i64) as u8;
-        let mut g = (255 - b - r) as u8;
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+/// A parsed format for the textual representation of values, created via
+/// `Graph::with_value_display_format`
+pub struct ValueFormat {
+    prefix: String,
+    suffix: String,
+    alignment: Option<char>,
+    sign: bool,
+    zero_padded: bool,
+    width: usize,
+    precision: Option<usize>,
+}
 
-        // Lighten up the values
-        b = b.saturating_add(soft_scale);
-        r = r.saturating_add(soft_scale);
-        g = g.saturating_add(soft_scale);
+impl ValueFormat {
+    /// Parses a format like `{:>+06.2}` including the surrounding text
+    fn parse(format: &str) -> Option<Self> {
+        let start = format.find('{')?;
+        let end = start + format[start..].find('}')?;
+        let (prefix, suffix) = (&format[..start], &format[end + 1..]);
+        if suffix.contains(['{', '}']) {
+            return None;
+        }
 
-        (r, g, b)
+        // Split the specification into alignment, flags, width and precision
+        let spec = &format[start + 1..end];
+        let spec = match spec.strip_prefix(':') {
+            Some(spec) => spec,
+            None if spec.is_empty() => spec,
+            None => return None,
+        };
+        let alignment = spec.chars().next().filter(|c| "<^>".contains(*c));
+        let spec = &spec[alignment.map_or(0, char::len_utf8)..];
+        let sign = spec.starts_with('+');
+        let spec = spec.strip_prefix('+').unwrap_or(spec);
+        let zero_padded = spec.starts_with('0');
+        let spec = spec.strip_prefix('0').unwrap_or(spec);
+        if !spec.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        let (width, precision) = match spec.find('.') {
+            Some(index) => (&spec[..index], Some(&spec[index + 1..])),
+            None => (spec, None),
+        };
+        let width = match width {
+            "" => 0,
+            width => width.parse().ok()?,
+        };
+        let precision = match precision {
+            Some(precision) => Some(precision.parse().ok()?),
+            None => None,
+        };
+
+        Some(ValueFormat {
+            prefix: prefix.to_owned(),
+            suffix: suffix.to_owned(),
+            alignment,
+            sign,
+            zero_padded,
+            width,
+            precision,
+        })
+    }
+
+    /// Formats the value
+    fn format(&self, value: f64) -> String {
+        let text = match self.precision {
+            Some(precision) => format!("{:.*}", precision, value),
+            None => value.to_string(),
+        };
+        let text = match (self.sign, text.starts_with('-')) {
+            (true, false) => format!("+{}", text),
+            _ => text,
+        };
+
+        // Zero padding goes behind the sign and overrides the alignment
+        let text = match self.alignment {
+            _ if self.zero_padded => {
+                let sign_len = usize::from(text.starts_with(['+', '-']));
+                let (sign, digits) = text.split_at(sign_len);
+                let width = self.width.saturating_sub(sign_len);
+                format!("{}{:0>2$}", sign, digits, width)
+            }
+            Some('<') => format!("{:<1$}", text, self.width),
+            Some('^') => format!("{:^1$}", text, self.width),
+            _ => format!("{:>1$}", text, self.width),
+        };
+        format!("{}{}{}", self.prefix, text, self.suffix)
+    }
+}
+
+/// A single rendered row of the graph
+struct RenderedRow {
+    /// The printable row
+    content: String,
+
+    /// The names of the lines at their column positions
+    labels: String,
+
+    /// The range of the visible columns
+    visible: ops::Range<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+/// A logical group of lines, created via `Graph::with_group`
+pub struct Group {
+    name: String,
+    line_names: Vec<String>,
+    color: Option<(u8, u8, u8)>,
+}
+
+impl Group {
+    /// Returns the group which contains the line
+    fn get<'a>(groups: &'a [Group], line_name: &str) -> Option<&'a Group> {
+        groups
+            .iter()
+            .find(|g| g.line_names.iter().any(|n| n == line_name))
+    }
+
+    /// Returns the name of the group which contains the line
+    fn find<'a>(groups: &'a [Group], line_name: &str) -> Option<&'a str> {
+        Group::get(groups, line_name).map(|g| g.name.as_str())
+    }
+
+    /// Returns the color of the group which contains the line
+    fn color(groups: &[Group], line_name: &str) -> Option<(u8, u8, u8)> {
+        Group::get(groups, line_name).and_then(|g| g.color)
+    }
+
+    /// Returns the label of the line including its group name
+    fn label(groups: &[Group], line_name: &str) -> String {
+        match Group::find(groups, line_name) {
+            Some(group_name) => format!("{}/{}", group_name, line_name),
+            None => line_name.to_owned(),
+        }
     }
 }
 
+#[derive(Clone)]
+/// A value transformation which is specific to a single line
+struct Transform<V>(Arc<dyn Fn(V) -> V + Send + Sync>);
+
+impl<V> fmt::Debug for Transform<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transform")
+    }
+}
+
+impl<V> PartialEq for Transform<V> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<V> Eq for Transform<V> {}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
-/// Representation of a set of data `Point` values
-struct Line<V> {
+/// A named line of values which occupies a single column of a `Graph`, and can
+/// be built up front to be inserted via `Graph::insert_line`
+pub struct Line<V> {
+    alignment: Option<LabelAlignment>,
     got_data: bool,
+    highlighted: bool,
     name: String,
     started: bool,
+    ticks_since_data: usize,
+    timestamps: Vec<Instant>,
+    transform: Option<Transform<V>>,
     values: Vec<V>,
 }
 
 impl<V> Line<V> {
-    /// Creates a new `Line`
-    fn new(name: &str) -> Self {
+    /// Creates a new `Line` without any values
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Line;
+    ///
+    /// let line: Line<u8> = Line::new("Line 1");
+    /// assert_eq!(line.name(), "Line 1");
+    /// ```
+    pub fn new(name: &str) -> Self {
         Line {
+            alignment: None,
             got_data: false,
+            highlighted: false,
             name: name.to_owned(),
             started: false,
+            ticks_since_data: 0,
+            timestamps: vec![],
+            transform: None,
             values: vec![],
         }
     }
 
     /// Adds a value to a line
-    fn add_value(&mut self, value: V) {
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Line;
+    ///
+    /// let mut line = Line::new("Line 1");
+    /// line.add_value(5);
+    /// assert_eq!(line.values(), &[5]);
+    /// ```
+    pub fn add_value(&mut self, value: V) {
         self.values.push(value);
         self.got_data = true;
+        self.ticks_since_data = 0;
+    }
+
+    /// Adds a value to a line together with the time it has been added
+    fn add_timed_value(&mut self, value: V, timestamp: Option<Instant>) {
+        self.add_value(value);
+        if let Some(timestamp) = timestamp {
+            self.timestamps.push(timestamp);
+        }
+    }
+
+    /// Appends the values of the other line, whereas the timestamps of this
+    /// line are kept only if all values of the other line have a timestamp
+    fn append(&mut self, other: Line<V>) {
+        if other.timestamps.len() == other.values.len() {
+            self.timestamps.extend(other.timestamps);
+        } else {
+            self.timestamps = other.timestamps;
+        }
+        self.values.extend(other.values);
+        self.got_data |= other.got_data;
+        self.ticks_since_data =
+            self.ticks_since_data.min(other.ticks_since_data);
+    }
+
+    /// Returns the time of the value at the index, whereas the timestamps
+    /// belong to the latest values
+    fn timestamp(&self, index: usize) -> Option<&Instant> {
+        let offset = self.values.len().saturating_sub(self.timestamps.len());
+        let skipped = self.timestamps.len().saturating_sub(self.values.len());
+        index
+            .checked_sub(offset)
+            .and_then(|index| self.timestamps.get(index + skipped))
+    }
+
+    /// Returns the latest values which have been added within the duration
+    fn windowed_values(&self, duration: Duration) -> &[V] {
+        let now = Instant::now();
+        let count = self
+            .timestamps
+            .iter()
+            .rev()
+            .take_while(|t| now.duration_since(**t) <= duration)
+            .count();
+        &self.values[self.values.len().saturating_sub(count)..]
+    }
+
+    /// Returns the name of the line
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns all values of the line
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+}
+
+/// Mutable access to the values of a line, which keeps the times of the
+/// values in sync with them
+///
+/// The values can be modified in place via dereferencing, whereas adding and
+/// removing values is only possible via the provided methods.
+pub struct ValuesMut<'a, V> {
+    values: &'a mut Vec<V>,
+    timestamps: &'a mut Vec<Instant>,
+    timestamp: Option<Instant>,
+}
+
+impl<V> ValuesMut<'_, V> {
+    /// Shortens the values to the provided length by removing the latest
+    /// ones
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    ///
+    /// for (_, mut values) in graph.columns_iter_mut() {
+    ///     values.truncate(1);
+    /// }
+    /// assert_eq!(&graph["Line 1"], &[1]);
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        let offset = self.offset();
+        self.values.truncate(len);
+        self.timestamps.truncate(len.saturating_sub(offset));
+    }
+
+    /// Removes all values
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    ///
+    /// for (_, mut values) in graph.columns_iter_mut() {
+    ///     values.clear();
+    /// }
+    /// assert!(graph["Line 1"].is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Keeps only the values for which the predicate returns true
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.add("Line 1", 1).unwrap();
+    /// graph.add("Line 1", 2).unwrap();
+    ///
+    /// for (_, mut values) in graph.columns_iter_mut() {
+    ///     values.retain(|v| v % 2 == 0);
+    /// }
+    /// assert_eq!(&graph["Line 1"], &[2]);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&V) -> bool,
+    {
+        let offset = self.offset();
+        let keep: Vec<bool> = self.values.iter().map(f).collect();
+        let mut keep_values = keep.iter();
+        self.values.retain(|_| *keep_values.next().unwrap());
+        let mut keep_timestamps = keep.iter().skip(offset);
+        self.timestamps
+            .retain(|_| *keep_timestamps.next().unwrap_or(&true));
+    }
+
+    /// Appends a value, which gets the current time if the graph keeps the
+    /// times of its values. Together with `clear` this allows resampling the
+    /// values of a line.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// for value in 1..=4 {
+    ///     graph.add("Line 1", value).unwrap();
+    /// }
+    ///
+    /// for (_, mut values) in graph.columns_iter_mut() {
+    ///     let sums: Vec<_> = values.chunks(2).map(|c| c.iter().sum()).collect();
+    ///     values.clear();
+    ///     for sum in sums {
+    ///         values.push(sum);
+    ///     }
+    /// }
+    /// assert_eq!(&graph["Line 1"], &[3, 7]);
+    /// ```
+    pub fn push(&mut self, value: V) {
+        self.values.push(value);
+        if let Some(timestamp) = self.timestamp {
+            self.timestamps.push(timestamp);
+        }
+    }
+
+    /// Returns the index of the first value which has a timestamp
+    fn offset(&self) -> usize {
+        self.values.len().saturating_sub(self.timestamps.len())
+    }
+}
+
+impl<V> Extend<V> for ValuesMut<'_, V> {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = V>,
+    {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<V> ops::Deref for ValuesMut<'_, V> {
+    type Target = [V];
+
+    fn deref(&self) -> &[V] {
+        self.values
+    }
+}
+
+impl<V> ops::DerefMut for ValuesMut<'_, V> {
+    fn deref_mut(&mut self) -> &mut [V] {
+        self.values
     }
 }
 
@@ -0,0 +1,80 @@
+//! Convenience macros for the graph construction
+
+/// Create a new `Graph` with an optional prefix length and initial lines
+///
+/// The line names have to be string literals, which are checked for
+/// uniqueness during compile time.
+///
+/// # Example
+/// ```
+/// use rain::rain_graph;
+///
+/// let graph = rain_graph! {
+///     prefix: 10,
+///     lines: {
+///         "CPU" => 45u8,
+///         "RAM" => 60u8,
+///     }
+/// };
+/// assert_eq!(graph.active_lines(), vec!["CPU", "RAM"]);
+///
+/// let graph = rain_graph! { lines: { "CPU" => 45u8 } };
+/// assert_eq!(graph.config().prefix_len, 11);
+/// ```
+///
+/// Duplicate line names fail to compile:
+/// ```compile_fail
+/// use rain::rain_graph;
+///
+/// let graph = rain_graph! { lines: { "CPU" => 1u8, "CPU" => 2u8 } };
+/// ```
+#[macro_export]
+macro_rules! rain_graph {
+    (prefix: $prefix:expr, lines: { $($name:literal => $value:expr),* $(,)? } $(,)?) => {{
+        const _: () = assert!(
+            $crate::unique_names(&[$($name),*]),
+            "line names have to be unique"
+        );
+        let mut graph = $crate::Graph::with_prefix_length($prefix);
+        $(graph
+            .add($name, $value)
+            .expect("adding a line to a new graph can not fail");)*
+        graph
+    }};
+    (lines: { $($name:literal => $value:expr),* $(,)? } $(,)?) => {
+        $crate::rain_graph! { prefix: 8, lines: { $($name => $value),* } }
+    };
+}
+
+#[doc(hidden)]
+/// Returns true if all names are different, which can be evaluated during
+/// compile time
+pub const fn unique_names(names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            if equal(names[i].as_bytes(), names[j].as_bytes()) {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Compares two byte slices during compile time
+const fn equal(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
@@ -0,0 +1,125 @@
+//! Thread safe access to a graph
+use crate::{ErrorType, Graph, RainResult};
+use std::{
+    convert, fmt,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A `Graph` which can be shared between multiple threads
+///
+/// # Example
+/// ```
+/// use rain::{Graph, SharedGraph};
+/// use std::thread;
+///
+/// let graph = SharedGraph::new(Graph::new());
+///
+/// let producer = graph.clone();
+/// thread::spawn(move || producer.add("Line 1", 0u8).is_ok())
+///     .join()
+///     .unwrap();
+///
+/// assert!(graph.remove("Line 1").is_ok());
+/// ```
+pub struct SharedGraph<V> {
+    graph: Arc<Mutex<Graph<V>>>,
+}
+
+impl<V> SharedGraph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: convert::From<V>,
+{
+    /// Create a new `SharedGraph` from an existing `Graph`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, SharedGraph};
+    ///
+    /// let _: SharedGraph<u8> = SharedGraph::new(Graph::new());
+    /// ```
+    pub fn new(graph: Graph<V>) -> Self {
+        SharedGraph {
+            graph: Arc::new(Mutex::new(graph)),
+        }
+    }
+
+    /// Add a data value to the graph, see `Graph::add`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, SharedGraph};
+    ///
+    /// let graph = SharedGraph::new(Graph::new());
+    /// assert_eq!(graph.add("Line 1", 0).unwrap(), "Line 1");
+    /// ```
+    pub fn add<T>(&self, identifier: T, value: V) -> RainResult<T>
+    where
+        T: fmt::Display,
+    {
+        self.lock()?.add(identifier, value)
+    }
+
+    /// Remove a line from the graph, see `Graph::remove`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, SharedGraph};
+    ///
+    /// let graph = SharedGraph::new(Graph::new());
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    /// assert_eq!(graph.remove("Line 1").unwrap(), "Line 1");
+    /// ```
+    pub fn remove<T>(&self, identifier: T) -> RainResult<T>
+    where
+        T: fmt::Display,
+    {
+        self.lock()?.remove(identifier)
+    }
+
+    /// Prints the graph, see `Graph::print`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, SharedGraph};
+    ///
+    /// let graph = SharedGraph::new(Graph::new());
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    ///
+    /// graph.print();
+    /// ```
+    pub fn print(&self) -> RainResult<()> {
+        self.lock()?.print()
+    }
+
+    /// Print only if new data is available, see `Graph::print_if_new_data`
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, SharedGraph};
+    ///
+    /// let graph = SharedGraph::new(Graph::new());
+    /// assert!(graph.add("Line 1", 0).is_ok());
+    ///
+    /// graph.print_if_new_data();
+    /// ```
+    pub fn print_if_new_data(&self) -> RainResult<bool> {
+        self.lock()?.print_if_new_data()
+    }
+
+    /// Acquire the lock of the graph
+    fn lock(&self) -> RainResult<MutexGuard<'_, Graph<V>>> {
+        match self.graph.lock() {
+            Ok(guard) => Ok(guard),
+            Err(_) => bail!(ErrorType::LockPoisoned, "Graph lock is poisoned"),
+        }
+    }
+}
+
+impl<V> Clone for SharedGraph<V> {
+    fn clone(&self) -> Self {
+        SharedGraph {
+            graph: Arc::clone(&self.graph),
+        }
+    }
+}
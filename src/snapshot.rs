@@ -0,0 +1,130 @@
+//! Recorded states of a graph
+use crate::Graph;
+#[cfg(not(feature = "tracing"))]
+use log::warn;
+use std::{convert, fmt};
+#[cfg(feature = "tracing")]
+use tracing::warn;
+
+/// The lines of a `Graph` together with their values at a certain time
+///
+/// # Example
+/// ```
+/// use rain::Graph;
+///
+/// let mut graph = Graph::new();
+/// graph.add("Line 1", 0).unwrap();
+///
+/// let snapshot = graph.snapshot();
+/// assert_eq!(snapshot.lines, vec![("Line 1".to_owned(), vec![0])]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Snapshot<V> {
+    /// The names and values of all active lines in column order
+    pub lines: Vec<(String, Vec<V>)>,
+}
+
+impl<V> Snapshot<V> {
+    /// Returns the values of the line or `None` if it is not part of the
+    /// snapshot
+    ///
+    /// # Example
+    /// ```
+    /// use rain::Snapshot;
+    ///
+    /// let snapshot = Snapshot {
+    ///     lines: vec![("Line 1".to_owned(), vec![1, 2])],
+    /// };
+    /// assert_eq!(snapshot.values("Line 1"), Some(&[1, 2][..]));
+    /// assert_eq!(snapshot.values("Line 2"), None);
+    /// ```
+    pub fn values(&self, name: &str) -> Option<&[V]> {
+        self.lines
+            .iter()
+            .find(|(line_name, _)| line_name == name)
+            .map(|(_, values)| values.as_slice())
+    }
+}
+
+/// The differences between two snapshots of a `Graph`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SnapshotDiff<V> {
+    /// The lines which exist only in the newer snapshot
+    pub added_lines: Vec<String>,
+
+    /// The lines which exist only in the older snapshot
+    pub removed_lines: Vec<String>,
+
+    /// The lines which got more values, together with the new values
+    pub changed_lines: Vec<(String, Vec<V>)>,
+}
+
+/// Feeds the values of a `Snapshot` back into a `Graph`, one tick at a time
+///
+/// # Example
+/// ```
+/// use rain::{Graph, Replay};
+///
+/// let mut recorded = Graph::new();
+/// recorded.add("Line 1", 1).unwrap();
+/// recorded.add("Line 1", 2).unwrap();
+///
+/// let mut graph = Graph::new();
+/// let mut replay = Replay::new(recorded.snapshot());
+/// while replay.step(&mut graph) {}
+/// assert!(graph == recorded);
+/// ```
+pub struct Replay<V> {
+    snapshot: Snapshot<V>,
+    tick: usize,
+}
+
+impl<V> Replay<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: convert::From<V>,
+{
+    /// Create a new `Replay` starting at the first tick of the snapshot
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Replay, Snapshot};
+    ///
+    /// let _: Replay<u8> = Replay::new(Snapshot::default());
+    /// ```
+    pub fn new(snapshot: Snapshot<V>) -> Self {
+        Replay { snapshot, tick: 0 }
+    }
+
+    /// Add the values of the current tick to the graph and advance to the
+    /// next one. Lines without a value for the tick are skipped. Returns
+    /// `false` if the snapshot has been exhausted.
+    ///
+    /// # Example
+    /// ```
+    /// use rain::{Graph, Replay, Snapshot};
+    ///
+    /// let mut graph = Graph::new();
+    /// let mut replay = Replay::new(Snapshot {
+    ///     lines: vec![("Line 1".to_owned(), vec![1])],
+    /// });
+    ///
+    /// assert!(replay.step(&mut graph));
+    /// assert!(!replay.step(&mut graph));
+    /// ```
+    pub fn step(&mut self, graph: &mut Graph<V>) -> bool {
+        let mut exhausted = true;
+        for (name, values) in &self.snapshot.lines {
+            if let Some(value) = values.get(self.tick) {
+                exhausted = false;
+                if let Err(e) = graph.add(name, value.clone()) {
+                    warn!("Unable to replay value of line '{}': {}", name, e);
+                }
+            }
+        }
+        if !exhausted {
+            self.tick += 1;
+        }
+        !exhausted
+    }
+}
@@ -1,11 +1,111 @@
+#[cfg(all(feature = "mowl", not(feature = "tracing")))]
 use log::LevelFilter;
-use rain::Graph;
-use rand::distributions::{Distribution, Range};
-use std::{thread, time::Duration};
+use rain::{
+    rain_graph, ErrorType, Graph, GraphConfig, GraphMetrics, LabelAlignment,
+    Line, OutputTarget, OverflowPolicy, RainError, Replay, SharedGraph,
+};
+use rand::distributions::{Distribution, Uniform};
+use std::{
+    error::Error,
+    fmt, io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Enable the logging of warnings if the logger is part of the graph
+fn with_logging<V>(graph: Graph<V>) -> Graph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: From<V>,
+{
+    #[cfg(all(feature = "mowl", not(feature = "tracing")))]
+    let graph = graph.set_log_level(LevelFilter::Warn);
+    graph
+}
+
+/// Detach the graph from the terminal by printing into a sink of fixed width
+fn detached<V>(graph: Graph<V>) -> Graph<V>
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: From<V>,
+{
+    graph
+        .with_fallback_size(120)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())))
+}
+
+/// Writer collecting everything a graph prints
+#[derive(Clone, Default)]
+struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedWriter {
+    /// Returns the whole printed output
+    fn output(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+    }
+
+    /// Returns the printed rows
+    fn rows(&self) -> Vec<String> {
+        self.output().lines().map(String::from).collect()
+    }
+
+    /// Returns the number of printed rows
+    fn lines(&self) -> usize {
+        self.rows().len()
+    }
+}
+
+/// Capture the output of the graph within the returned writer
+fn captured<V>(graph: Graph<V>) -> (Graph<V>, SharedWriter)
+where
+    V: Clone + Default + Ord + PartialEq + fmt::Debug,
+    f64: From<V>,
+{
+    let writer = SharedWriter::default();
+    let graph =
+        graph.with_output(OutputTarget::Custom(Box::new(writer.clone())));
+    (graph, writer)
+}
+
+/// Remove all ANSI escape sequences from a rendered row
+fn strip_ansi(row: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = row.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// Returns the character positions of all bold glyphs within a rendered row
+fn bold_positions(row: &str) -> Vec<usize> {
+    let parts: Vec<_> = row.split("\x1b[1m").collect();
+    (1..parts.len())
+        .map(|i| strip_ansi(&parts[..i].concat()).chars().count())
+        .collect()
+}
 
 #[test]
 fn add_remove_success_1() {
-    let mut graph = Graph::new().set_log_level(LevelFilter::Warn);
+    let mut graph = detached(with_logging(Graph::new()));
     assert!(graph.add("Line 1", 0).is_ok());
     assert!(graph.print().is_ok());
     assert!(graph.add("Line 2", 0).is_ok());
@@ -30,7 +130,7 @@ fn add_remove_success_1() {
 
 #[test]
 fn add_remove_success_2() {
-    let mut graph = Graph::new().set_log_level(LevelFilter::Warn);
+    let mut graph = detached(with_logging(Graph::new()));
     for i in 1..50 {
         if i > 25 {
             assert!(graph.remove(&(i - 25).to_string()).is_ok());
@@ -44,7 +144,7 @@ fn add_remove_success_2() {
 
 #[test]
 fn add_remove_success_3() {
-    let mut graph = Graph::new();
+    let mut graph = detached(Graph::new());
     assert!(graph.add("0", 100).is_ok());
     for i in 1..101 {
         assert!(graph.add("0", i).is_ok());
@@ -56,7 +156,7 @@ fn add_remove_success_3() {
 
 #[test]
 fn add_remove_success_4() {
-    let mut graph = Graph::new();
+    let mut graph = detached(Graph::new());
     let l1 = "Line 1";
     let l2 = "Line 2";
     assert!(graph.add(l1, 0).is_ok());
@@ -79,7 +179,7 @@ fn add_remove_success_4() {
 
 #[test]
 fn print_long_prefix_success() {
-    let mut graph = Graph::with_prefix_length(18);
+    let mut graph = detached(Graph::with_prefix_length(18));
     assert!(graph.add("Line 1", 0).is_ok());
     assert!(graph.print().is_ok());
     assert!(graph.add("A very long prefix", 0).is_ok());
@@ -88,29 +188,29 @@ fn print_long_prefix_success() {
 
 #[test]
 fn print_if_new_data_success() {
-    let mut graph = Graph::new();
+    let mut graph = detached(Graph::new());
     let l1 = "Line 1";
     let l2 = "Line 2";
 
     assert!(graph.add(l1, 0).is_ok());
     assert!(graph.add(l2, 0).is_ok());
-    assert_eq!(graph.print_if_new_data().unwrap(), true);
-    assert_eq!(graph.print_if_new_data().unwrap(), false);
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(!graph.print_if_new_data().unwrap());
 
     assert!(graph.remove(l2).is_ok());
-    assert_eq!(graph.print_if_new_data().unwrap(), true);
-    assert_eq!(graph.print_if_new_data().unwrap(), false);
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(!graph.print_if_new_data().unwrap());
 
     assert!(graph.add(l2, 0).is_ok());
-    assert_eq!(graph.print_if_new_data().unwrap(), true);
-    assert_eq!(graph.print_if_new_data().unwrap(), false);
-    assert_eq!(graph.print_if_new_data().unwrap(), false);
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(!graph.print_if_new_data().unwrap());
+    assert!(!graph.print_if_new_data().unwrap());
 }
 
 #[test]
 fn random_add_remove_success() {
-    let mut graph = Graph::new();
-    let between = Range::new(0, 10);
+    let mut graph = detached(Graph::new());
+    let between = Uniform::new(0, 10);
     let mut rng = rand::thread_rng();
 
     for _ in 0..100 {
@@ -131,7 +231,7 @@ fn random_add_remove_success() {
 
 #[test]
 fn add_remove_success_signed_integer() {
-    let mut graph = Graph::new();
+    let mut graph = detached(Graph::new());
     let l1 = "L 1";
     let l2 = "L 2";
     let l3 = "Too long line 3";
@@ -148,3 +248,2319 @@ fn add_remove_success_signed_integer() {
     assert!(graph.remove(l3).is_ok());
     assert!(graph.print().is_ok());
 }
+
+#[test]
+fn highlight_line_success() {
+    let mut graph = detached(Graph::new());
+    let l1 = "Line 1";
+    let l2 = "Line 2";
+    assert!(graph.add(l1, 0).is_ok());
+    assert!(graph.add(l2, 5).is_ok());
+    assert!(graph.highlight_line(l1).is_ok());
+    for _ in 0..3 {
+        let rows = graph.render().unwrap();
+        assert_eq!(bold_positions(&rows[0]), vec![11]);
+        assert!(rows[0].contains("\x1b[38;2;125;125;255m\x1b[1m"));
+        assert!(graph.print().is_ok());
+    }
+    assert!(graph.unhighlight_line(l1).is_ok());
+    let rows = graph.render().unwrap();
+    assert!(bold_positions(&rows[0]).is_empty());
+    assert!(graph.print().is_ok());
+    assert!(graph.highlight_line("Line 3").is_err());
+}
+
+#[test]
+fn bold_latest_success() {
+    let mut graph = detached(Graph::new().with_bold_latest());
+    let l1 = "Line 1";
+    let l2 = "Line 2";
+    assert!(graph.add(l1, 0).is_ok());
+    assert!(graph.add(l2, 5).is_ok());
+    assert_eq!(bold_positions(&graph.render().unwrap()[0]), vec![11, 13]);
+    assert!(graph.print().is_ok());
+    assert!(bold_positions(&graph.render().unwrap()[0]).is_empty());
+    for i in 0..3 {
+        assert!(graph.add(l1, i).is_ok());
+        assert_eq!(bold_positions(&graph.render().unwrap()[0]), vec![11]);
+        assert!(graph.print().is_ok());
+        assert!(graph.add(l2, i).is_ok());
+        assert_eq!(bold_positions(&graph.render().unwrap()[0]), vec![13]);
+        assert!(graph.print().is_ok());
+    }
+}
+
+#[test]
+fn axis_success() {
+    let mut graph = detached(Graph::new().with_axis(0));
+    let l1 = "Line 1";
+    assert!(graph.add(l1, -5).is_ok());
+    for i in -2..3 {
+        assert!(graph.add(l1, i).is_ok());
+        let row = strip_ansi(&graph.render().unwrap()[0]);
+        assert_eq!(row.contains('┼'), i == 0, "{}", row);
+        assert!(graph.print().is_ok());
+    }
+}
+
+#[test]
+fn group_separator_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_group_separator(2).with_fallback_size(30));
+    for i in 0..5 {
+        assert!(graph.add(i, i).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.remove(1).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            "       0 → ┬┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈",
+            "       1 → ╎┈┬┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈",
+            "       2 → ╎┈╎┈┈┬┈┈┈┈┈┈┈┈┈┈┈┈┈",
+            "       3 → ╎┈╎┈┈╎┈┬┈┈┈┈┈┈┈┈┈┈┈",
+            "       4 → ╎┈╎┈┈╎┈╎┈┈┬┈┈┈┈┈┈┈┈",
+            "       1 ← ╎┈┴┈┈╎┈╎┈┈╎┈┈┈┈┈┈┈┈",
+            "           ╎┈┈┈╎┈┈╎┈╎┈┈┈┈┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn print_count_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line 1", 0).is_ok());
+    for i in 0..5 {
+        assert_eq!(graph.print_count(), i);
+        assert!(graph.print().is_ok());
+    }
+    assert!(!graph.print_if_new_data().unwrap());
+    assert_eq!(graph.print_count(), 5);
+    graph.reset_print_count();
+    assert_eq!(graph.print_count(), 0);
+}
+
+#[test]
+fn auto_prefix_length_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_auto_prefix_length()
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("A", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("Longer", 1).is_ok());
+    assert!(graph.print().is_ok());
+
+    // The automatic prefix length never touches the configured one
+    assert_eq!(graph.config().prefix_len, 11);
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].starts_with("A → "));
+    assert!(rows[1].starts_with("Longer → "));
+
+    assert!(graph.add("A much longer line name", 1).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph
+        .add("A line name which exceeds the maximum length", 2)
+        .is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("A much longer line name").is_ok());
+    assert!(graph.print().is_ok());
+}
+
+#[test]
+fn auto_prefix_length_bounds_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_auto_prefix_length()
+            .with_min_prefix_length(4)
+            .with_max_prefix_length(10)
+            .with_fallback_size(20),
+    );
+    assert!(graph.add("A", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add("A much longer line name", 1).is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    // The short name is padded to the minimum, the long one truncated
+    assert_eq!(
+        writer.rows(),
+        vec!["   A → ┬┈┈┈┈┈┈┈┈┈┈┈┈", "A much lon → ╎┈┬┈┈┈┈",]
+    );
+}
+
+#[test]
+fn print_multibyte_prefix_success() {
+    let (mut graph, writer) =
+        captured(Graph::with_prefix_length(5).with_fallback_size(20));
+    for (name, value) in &[("日本語のライン", 0), ("🌧🌧🌧🌧🌧🌧", 1), ("Ünïcödé", 2)]
+    {
+        assert!(graph.add(name, *value).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            " 日本 → ┬┈┈┈┈┈┈┈┈┈┈┈",
+            "🌧🌧🌧🌧🌧 → ╎┈┬┈┈┈┈┈┈┈┈┈",
+            "Ünïcö → ╎┈╎┈┬┈┈┈┈┈┈┈",
+            "        ╎┈╎┈╎┈┈┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn left_aligned_prefix_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_left_aligned_prefix()
+            .with_fallback_size(20),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add("A very long line name", 1).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let mut graph = graph.with_right_aligned_prefix();
+    assert!(graph.add("Line 2", 2).is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            "Line 1   → ┬┈┈┈┈┈┈┈┈",
+            "A very l → ╎┈┬┈┈┈┈┈┈",
+            "Line 1   ← ┴┈╎┈┈┈┈┈┈",
+            "  Line 2 → ┬┈╎┈┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn no_prefix_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_no_prefix()
+            .with_auto_prefix_length()
+            .with_fallback_size(10),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add("Line 2", 1).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows, vec!["┬┈┈┈┈┈┈┈┈┈", "╎┈┬┈┈┈┈┈┈┈", "┴┈╎┈┈┈┈┈┈┈"]);
+}
+
+#[test]
+fn timestamp_prefix_success() {
+    // Split the row behind the timestamp and ensure its digits and separators
+    fn split_timestamp(row: &str, separators: &str) -> String {
+        let (timestamp, rest) = row.split_at(separators.len() + 1);
+        for (c, separator) in timestamp.chars().zip(separators.chars()) {
+            match separator {
+                '0' => assert!(c.is_ascii_digit(), "{}", row),
+                _ => assert_eq!(c, separator, "{}", row),
+            }
+        }
+        assert!(timestamp.ends_with(' '));
+        rest.to_owned()
+    }
+
+    let (mut graph, writer) =
+        captured(Graph::with_prefix_length(6).with_fallback_size(40));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    let plain = &writer.rows()[0];
+    assert!(plain.starts_with("Line 1 → ┬"));
+    assert_eq!(plain.chars().count(), 40);
+
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(6)
+            .with_timestamp_prefix()
+            .with_fallback_size(40),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    let rest = split_timestamp(&writer.rows()[0], "00:00:00");
+    assert!(rest.starts_with("Line 1 → ┬"));
+    assert_eq!(rest.chars().count(), 40 - 9);
+
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(6)
+            .with_timestamp_format("%Y-%m-%d %H:%M:%S")
+            .with_fallback_size(40),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    let rest = split_timestamp(&writer.rows()[0], "0000-00-00 00:00:00");
+    assert!(rest.starts_with("Line 1 → ┬"));
+    assert_eq!(rest.chars().count(), 40 - 20);
+
+    let mut graph = detached(Graph::new().with_timestamp_format("%Q"));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_err());
+}
+
+#[test]
+fn tick_counter_prefix_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(6)
+            .with_tick_counter_prefix()
+            .with_fallback_size(40),
+    );
+    for i in 0..3 {
+        assert!(graph.add("Line 1", i).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with("[000000] Line 1 → ┬"));
+    for (i, row) in rows.iter().enumerate() {
+        assert!(row.starts_with(&format!("[{:06}] ", i)), "{}", row);
+    }
+
+    // The counter follows the timestamp
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(6)
+            .with_timestamp_prefix()
+            .with_tick_counter_prefix()
+            .with_fallback_size(40),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_n_times(2).is_ok());
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 2);
+    for (i, row) in rows.iter().enumerate() {
+        let (timestamp, rest) = row.split_at(9);
+        assert!(
+            timestamp.ends_with(' ') && timestamp.contains(':'),
+            "{}",
+            row
+        );
+        assert!(rest.starts_with(&format!("[{:06}] ", i)), "{}", row);
+    }
+}
+
+#[test]
+fn output_target_success() {
+    let mut graph = Graph::new().with_fallback_size(120).with_stderr_output();
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+
+    let mut graph = Graph::new()
+        .with_fallback_size(80)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+}
+
+#[test]
+fn rate_limit_success() {
+    let mut graph =
+        detached(Graph::new().with_rate_limit(Duration::from_millis(100)));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(graph.add("Line 1", 1).is_ok());
+    assert!(!graph.print_if_new_data().unwrap());
+    thread::sleep(Duration::from_millis(100));
+    assert!(graph.print_if_new_data().unwrap());
+
+    let start = Instant::now();
+    assert!(graph.print().is_ok());
+    assert!(start.elapsed() >= Duration::from_millis(90));
+}
+
+#[test]
+fn debounce_success() {
+    let mut graph =
+        detached(Graph::new().with_debounce(Duration::from_millis(50)));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.add("Line 2", 0).is_ok());
+    assert!(!graph.print_if_new_data().unwrap());
+    thread::sleep(Duration::from_millis(50));
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(!graph.print_if_new_data().unwrap());
+}
+
+#[test]
+fn is_tty_success() {
+    let graph: Graph<u8> =
+        Graph::new().with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert!(!graph.is_tty());
+}
+
+#[test]
+fn fallback_size_success() {
+    let mut graph = Graph::new()
+        .with_fallback_size(80)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+}
+
+#[test]
+fn fallback_size_custom_output_success() {
+    let mut graph = Graph::with_prefix_length(4)
+        .with_fallback_size(40)
+        .with_overflow_policy(OverflowPolicy::Error)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    match graph.print().unwrap_err().code {
+        ErrorType::ColumnsOverflow { visible, total } => {
+            assert_eq!(visible, 15);
+            assert_eq!(total, 20);
+        }
+        code => panic!("unexpected error {:?}", code),
+    }
+}
+
+#[test]
+fn no_resize_success() {
+    let widths = |writer: &SharedWriter| {
+        writer
+            .rows()
+            .iter()
+            .map(|row| row.chars().count())
+            .collect::<Vec<_>>()
+    };
+
+    // Without caching every print uses the latest width
+    let (mut graph, writer) =
+        captured(Graph::with_prefix_length(4).with_fallback_size(20));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    graph = graph.with_fallback_size(30);
+    assert!(graph.print_raw().is_ok());
+    assert_eq!(widths(&writer), vec![20, 30]);
+
+    // The width of the first print stays until a resize check is forced
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_no_resize()
+            .with_fallback_size(20),
+    );
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    graph = graph.with_fallback_size(30);
+    assert!(graph.print_raw().is_ok());
+    graph.force_resize_check();
+    assert!(graph.print_raw().is_ok());
+    assert_eq!(widths(&writer), vec![20, 20, 30]);
+}
+
+#[test]
+fn metrics_success() {
+    let mut graph = detached(Graph::new().with_metrics_enabled());
+    assert!(graph.add("Line 1", 0).is_ok());
+    for _ in 0..3 {
+        assert!(graph.print().is_ok());
+    }
+    let metrics = graph.metrics().unwrap();
+    assert_eq!(metrics.render_count, 3);
+    assert!(metrics.min_render_ns <= metrics.max_render_ns);
+    assert!(metrics.total_render_ns >= u128::from(metrics.max_render_ns));
+
+    graph.reset_metrics();
+    assert_eq!(graph.metrics(), Some(&GraphMetrics::default()));
+
+    let graph: Graph<u8> = Graph::new();
+    assert!(graph.metrics().is_none());
+}
+
+#[test]
+fn render_duration_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.last_render_duration().is_none());
+    assert!(graph.print().is_ok());
+    assert!(graph.last_render_duration().is_some());
+    assert!(graph.average_render_duration().is_none());
+
+    let mut graph = detached(Graph::new().with_metrics_enabled());
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.average_render_duration().is_some());
+}
+
+#[test]
+fn error_failure() {
+    let mut graph: Graph<u8> = Graph::new();
+    let err = graph.remove("Line 1").unwrap_err();
+    assert_eq!(
+        err.code,
+        ErrorType::LineDoesNotExist {
+            name: "Line 1".to_owned()
+        }
+    );
+    assert!(err.source().is_none());
+
+    let mut graph = Graph::new().with_timestamp_format("%Q");
+    assert!(graph.add("Line 1", 0).is_ok());
+    let err = graph.print().unwrap_err();
+    assert_eq!(err.code, ErrorType::Other);
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn max_columns_failure() {
+    let mut graph = detached(Graph::new().with_max_columns(2));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.add("Line 2", 0).is_ok());
+    assert!(graph.add("Line 2", 1).is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(
+        graph.add("Line 3", 0).unwrap_err().code,
+        ErrorType::MaxColumnsExceeded {
+            limit: 2,
+            attempted: 3
+        }
+    );
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("Line 3", 0).is_ok());
+}
+
+#[test]
+fn prefix_too_long_failure() {
+    assert!(Graph::<u8>::try_with_prefix_length(10).is_ok());
+    match Graph::<u8>::try_with_prefix_length(usize::from(u16::MAX)) {
+        Err(e) => match e.code {
+            ErrorType::PrefixTooLong { length, max_length } => {
+                assert_eq!(length, usize::from(u16::MAX));
+                assert!(max_length < length);
+            }
+            _ => panic!("Wrong error type"),
+        },
+        _ => panic!("Prefix should be too long"),
+    }
+}
+
+#[test]
+fn boxed_error_failure() {
+    fn remove() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut graph: Graph<u8> = Graph::new();
+        graph.remove("Line 1")?;
+        Ok(())
+    }
+    let err = remove().unwrap_err();
+    assert!(err.downcast_ref::<RainError>().is_some());
+}
+
+#[test]
+fn error_handler_success() {
+    let errors = Arc::new(AtomicUsize::new(0));
+    let handler_errors = errors.clone();
+    let mut graph = Graph::new()
+        .with_timestamp_format("%Q")
+        .with_error_handler(move |_| {
+            handler_errors.fetch_add(1, Ordering::SeqCst);
+        });
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(!graph.print_if_new_data().unwrap());
+    assert_eq!(errors.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn shared_graph_success() {
+    let graph = SharedGraph::new(detached(Graph::new()));
+    let threads: Vec<_> = (0..4)
+        .map(|i| {
+            let graph = graph.clone();
+            thread::spawn(move || {
+                for value in 0..10 {
+                    assert!(graph.add(i, value).is_ok());
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        assert!(thread.join().is_ok());
+    }
+    assert!(graph.print_if_new_data().unwrap());
+    assert!(graph.remove(0).is_ok());
+    assert!(graph.print().is_ok());
+}
+
+#[test]
+fn shared_graph_poisoned_failure() {
+    struct Panicking;
+    impl fmt::Display for Panicking {
+        fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+            panic!("Identifier can not be displayed")
+        }
+    }
+
+    let graph: SharedGraph<u8> = SharedGraph::new(Graph::new());
+    let poisoner = graph.clone();
+    assert!(thread::spawn(move || poisoner.add(Panicking, 0).is_ok())
+        .join()
+        .is_err());
+    assert_eq!(
+        graph.add("Line 1", 0).unwrap_err().code,
+        ErrorType::LockPoisoned
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn add_stream_success() {
+    use futures::{executor::block_on, stream};
+
+    let mut graph = detached(Graph::new());
+    let values = stream::iter(0..10);
+    assert!(block_on(graph.add_stream("Line 1", values)).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+}
+
+#[test]
+fn export_svg_success() {
+    let mut graph = Graph::new();
+    assert!(graph.add("Line <1>", 0).is_ok());
+    assert!(graph.add("Line <1>", 10).is_ok());
+    assert!(graph.add("Line 2", 5).is_ok());
+
+    let mut svg = vec![];
+    assert!(graph.export_svg(10, &mut svg).is_ok());
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.contains(r#"width="20" height="20""#));
+    assert!(svg.contains("<title>Line &lt;1&gt;</title>"));
+    assert_eq!(svg.matches("<rect").count(), 3);
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn sparkline_mode_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_sparkline_mode().with_fallback_size(20));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    for i in 0..8 {
+        assert!(graph.add("Line 1", i).is_ok());
+        assert!(graph.add("Line 2", 7 - i).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            "  Line 1 → ▁┈┈┈┈┈┈┈┈",
+            "  Line 2 → ▁┈█┈┈┈┈┈┈",
+            "           ▂┈▇┈┈┈┈┈┈",
+            "           ▃┈▆┈┈┈┈┈┈",
+            "           ▄┈▅┈┈┈┈┈┈",
+            "           ▅┈▄┈┈┈┈┈┈",
+            "           ▆┈▃┈┈┈┈┈┈",
+            "           ▇┈▂┈┈┈┈┈┈",
+            "           █┈▁┈┈┈┈┈┈",
+            "  Line 1 ← █┈▁┈┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn braille_mode_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_braille_mode().with_fallback_size(20));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print_raw().is_ok());
+    for i in 0..6 {
+        assert!(graph.add("Line 1", i % 4).is_ok());
+        assert!(graph.add("Line 2", 6 - i).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            "  Line 1 → ⠁┈┈┈┈┈┈┈┈",
+            "  Line 2 → ⠃┈⠁┈┈┈┈┈┈",
+            "           ⠇┈⠁┈┈┈┈┈┈",
+            "           ⡄┈⠃┈┈┈┈┈┈",
+            "           ⡆┈⠃┈┈┈┈┈┈",
+            "           ⠇┈⠃┈┈┈┈┈┈",
+            "           ⡃┈⠁┈┈┈┈┈┈",
+            "  Line 1 ← ⡃┈⠁┈┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn derivative_mode_success() {
+    let mut graph = detached(Graph::new().with_derivative_mode());
+    assert!(graph.add("Line 1", 0u32).is_ok());
+    assert!(graph.print().is_ok());
+    for i in 1..10 {
+        assert!(graph.add("Line 1", i * i).is_ok());
+        assert!(graph.add("Line 2", 100 - i).is_ok());
+        assert!(graph.print().is_ok());
+    }
+
+    // The steadily growing line is the hottest, the falling one the coldest
+    let rows = graph.render().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert!(rows[0].starts_with(
+        "           \x1b[38;2;255;125;125m╎\x1b[39m\x1b[38;5;8m┈\x1b[39m\
+         \x1b[38;2;125;125;255m╎"
+    ));
+}
+
+#[test]
+fn cumulative_mode_success() {
+    let mut graph = Graph::new().with_cumulative_mode();
+    for i in 0..5 {
+        assert!(graph.add("Line 1", i).is_ok());
+        assert!(graph.add("Line 2", -1).is_ok());
+    }
+    assert_eq!(&graph["Line 1"], &[0, 1, 3, 6, 10]);
+    assert_eq!(&graph["Line 2"], &[-1, -2, -3, -4, -5]);
+    let graph = detached(graph);
+    assert!(graph.render().unwrap()[0].starts_with(
+        "  Line 2 → \x1b[38;2;255;125;125m┬\x1b[39m\x1b[38;5;8m┈\x1b[39m\
+         \x1b[38;2;125;125;255m┬"
+    ));
+
+    let mut graph: Graph<u8> = Graph::new().with_cumulative_mode();
+    for _ in 0..3 {
+        assert!(graph.add("Line 1", 200).is_ok());
+    }
+    assert_eq!(&graph["Line 1"], &[200, 255, 255]);
+
+    let mut graph: Graph<i8> = Graph::new().with_cumulative_mode();
+    assert!(graph.add("Line 1", -100).is_ok());
+    assert!(graph.add("Line 1", -100).is_ok());
+    assert_eq!(&graph["Line 1"], &[-100, -128]);
+}
+
+#[test]
+fn value_clamping_success() {
+    let mut graph = detached(Graph::new().with_value_clamping(-10, 10));
+    assert!(graph.add("Line 1", -100).is_ok());
+    assert!(graph.add("Line 1", 100).is_ok());
+    assert!(graph.print().is_ok());
+
+    let mut graph = detached(
+        Graph::new()
+            .with_value_clamping(0, 10)
+            .with_strict_clamping(),
+    );
+    assert!(graph.add("Line 1", 5).is_ok());
+    assert_eq!(
+        graph.add("Line 1", 11).unwrap_err().code,
+        ErrorType::ValueClamped {
+            name: "Line 1".to_owned()
+        }
+    );
+}
+
+#[test]
+fn merge_lines_success() {
+    let mut graph = detached(Graph::new());
+    for value in &[1, 2, 3] {
+        assert!(graph.add("rx_bytes", *value).is_ok());
+    }
+    assert!(graph.add("tx_bytes", 10).is_ok());
+    assert!(graph.add("tx_bytes", 20).is_ok());
+    assert!(graph.add("errors", 0).is_ok());
+    assert!(graph.print().is_ok());
+
+    // Values are combined pairwise from the start as f(source, destination)
+    assert!(graph
+        .merge_lines("rx_bytes", "tx_bytes", |a, b| a * 100 + b)
+        .is_ok());
+    assert_eq!(graph.line_values("tx_bytes"), Some(&[10, 20, 110, 220][..]));
+    assert_eq!(graph.line_values("errors"), Some(&[0][..]));
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("rx_bytes").is_err());
+    assert_eq!(graph.all_lines(), vec!["tx_bytes", "errors"]);
+    assert_eq!(graph.column_index("tx_bytes"), Some(1));
+
+    assert!(graph.merge_lines("tx_bytes", "tx_bytes", |a, _| a).is_err());
+    assert!(graph.merge_lines("foo", "tx_bytes", |a, _| a).is_err());
+    assert!(graph.merge_lines("tx_bytes", "bar", |a, _| a).is_err());
+}
+
+#[test]
+fn merge_lines_pending_removal_failure() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("src", 1).is_ok());
+    assert!(graph.add("dst", 2).is_ok());
+    assert!(graph.add("other", 3).is_ok());
+    assert!(graph.print().is_ok());
+
+    // A failed merge leaves both lines untouched
+    assert!(graph.remove("src").is_ok());
+    match graph.merge_lines("src", "dst", |a, b| a + b) {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::DuplicateRemoval {
+                name: "src".to_owned()
+            }
+        ),
+        Ok(_) => panic!("merged a line which is about to be removed"),
+    }
+    assert_eq!(graph.line_values("dst"), Some(&[2][..]));
+
+    assert!(graph.remove("dst").is_ok());
+    match graph.merge_lines("other", "dst", |a, b| a + b) {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::LineDoesNotExist {
+                name: "dst".to_owned()
+            }
+        ),
+        Ok(_) => panic!("merged into a line which is about to be removed"),
+    }
+    assert_eq!(graph.line_values("dst"), Some(&[2][..]));
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.all_lines(), vec!["other"]);
+}
+
+#[test]
+fn value_transform_success() {
+    let mut graph = detached(
+        Graph::new()
+            .with_value_transform(|v| v / 1024)
+            .with_value_clamping(0, 10)
+            .with_strict_clamping(),
+    );
+    assert!(graph.add("Line 1", 10 * 1024).is_ok());
+    assert!(graph.add("Line 1", 11 * 1024).is_err());
+    assert!(graph.print().is_ok());
+}
+
+#[test]
+fn per_line_transform_success() {
+    let mut graph = detached(
+        Graph::new()
+            .with_value_transform(|v| v / 1024)
+            .with_value_clamping(0, 10)
+            .with_strict_clamping(),
+    );
+    assert!(graph.add("Bytes", 1024).is_ok());
+    assert!(graph.add("Millis", 1000).is_ok());
+    assert!(graph
+        .with_per_line_transform("Millis", |v| v / 1000)
+        .is_ok());
+    assert!(graph.add("Millis", 10 * 1000).is_ok());
+    assert!(graph.add("Millis", 11 * 1000).is_err());
+    assert!(graph.print().is_ok());
+    assert!(graph.with_per_line_transform("Other", |v| v).is_err());
+}
+
+#[test]
+fn group_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_auto_prefix_length()
+            .with_group("CPU", &["cpu0", "cpu1"])
+            .with_group("Network", &["eth0"])
+            .with_fallback_size(30),
+    );
+    for name in &["cpu0", "eth0", "cpu1", "other"] {
+        assert!(graph.add(name, 0).is_ok());
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.remove("cpu0").is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(
+        rows,
+        vec![
+            "CPU/cpu0 → ┬┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈┈",
+            "Network/eth0 → ╎┈┈┬┈┈┈┈┈┈┈┈┈┈┈",
+            "    CPU/cpu1 → ╎┈┈╎┈┈┬┈┈┈┈┈┈┈┈",
+            "       other → ╎┈┈╎┈┈╎┈┈┬┈┈┈┈┈",
+            "    CPU/cpu0 ← ┴┈┈╎┈┈╎┈┈╎┈┈┈┈┈",
+        ]
+    );
+}
+
+#[test]
+fn group_color_success() {
+    let mut graph = detached(
+        Graph::new()
+            .with_group("Memory", &["used"])
+            .with_group_color("Memory", (0, 0, 255))
+            .with_group_color("CPU", (0, 255, 0)),
+    );
+    assert!(graph.add_to_group("Memory", "cached").is_ok());
+    assert!(graph.add_to_group("CPU", "cpu0").is_ok());
+    for (name, value) in
+        &[("used", 0), ("cached", 5), ("cpu0", 9), ("other", 3)]
+    {
+        assert!(graph.add(name, *value).is_ok());
+        assert!(graph.print().is_ok());
+    }
+    // Group colors win over the value gradient
+    let rows = graph.render().unwrap();
+    assert!(rows[0].starts_with(
+        "           \x1b[38;2;0;0;255m╎\x1b[39m\x1b[38;5;8m┈\x1b[39m\
+         \x1b[38;2;0;0;255m╎\x1b[39m\x1b[38;5;8m┈\x1b[39m\x1b[38;5;8m┈\x1b[39m\
+         \x1b[38;2;0;255;0m╎\x1b[39m\x1b[38;5;8m┈\x1b[39m\x1b[38;5;8m┈\x1b[39m\
+         \x1b[38;2;125;255;210m╎"
+    ));
+    assert_eq!(
+        graph.add_to_group("Network", "eth0").unwrap_err().code,
+        ErrorType::GroupDoesNotExist {
+            name: "Network".to_owned()
+        }
+    );
+}
+
+#[test]
+fn config_success() {
+    let first: Graph<u8> = Graph::with_prefix_length(4)
+        .with_fallback_size(80)
+        .with_bold_latest()
+        .with_sparkline_mode();
+    let mut second: Graph<u8> = Graph::new_with_config(first.config().clone())
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert_eq!(first.config(), second.config());
+    assert_eq!(second.config().prefix_len, 7);
+
+    second.config_mut().soft_scale = 0;
+    assert!(second.add("Line", 1).is_ok());
+    assert!(second.print().is_ok());
+    assert_ne!(first.config(), second.config());
+
+    // Groups and value formats are part of the configuration as well
+    let first: Graph<u8> = Graph::new()
+        .with_group("CPU", &["cpu0"])
+        .with_group_color("CPU", (0, 255, 0))
+        .with_value_display_format("{:.1}")
+        .unwrap();
+    let mut second: Graph<u8> = Graph::new_with_config(first.config().clone())
+        .with_fallback_size(80)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert_eq!(second.format_value(42), "42.0");
+    assert!(second.add("cpu0", 1).is_ok());
+    let rows = second.render().unwrap();
+    assert!(rows[0].starts_with("CPU/cpu0 → \x1b[38;2;0;255;0m┬"));
+    assert_ne!(first.config(), second.config());
+    assert_ne!(first.config(), Graph::<u8>::new().config());
+}
+
+#[test]
+fn config_default_success() {
+    let config = GraphConfig::default();
+    assert_eq!(config.prefix_len, 11);
+    assert_eq!(config.soft_scale, 125);
+    assert_eq!(&config, Graph::<u8>::new().config());
+}
+
+#[test]
+fn apply_config_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line", 1).is_ok());
+    assert!(graph.print().is_ok());
+
+    graph.apply_config(GraphConfig {
+        braille: true,
+        soft_scale: 0,
+        fallback_width: Some(120),
+        ..GraphConfig::default()
+    });
+    assert!(graph.config().braille);
+    assert_eq!(graph.print_count(), 1);
+    assert!(graph.add("Line", 2).is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.print_count(), 2);
+}
+
+#[test]
+fn column_index_success() {
+    let mut graph = detached(Graph::new());
+    for name in &["Line 1", "Line 2", "Line 3"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 2").is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.column_index("Line 1"), Some(0));
+    assert_eq!(graph.column_index("Line 2"), None);
+    assert_eq!(graph.column_index("Line 3"), Some(2));
+}
+
+#[test]
+fn active_lines_success() {
+    let mut graph = detached(Graph::new());
+    for name in &["Line 1", "Line 2", "Line 3"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 2").is_ok());
+    assert_eq!(graph.active_lines(), vec!["Line 1", "Line 3"]);
+    assert_eq!(graph.all_lines(), vec!["Line 1", "Line 2", "Line 3"]);
+
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.all_lines(), vec!["Line 1", "Line 3"]);
+}
+
+#[test]
+fn is_empty_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.is_empty());
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(!graph.is_empty());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.is_empty());
+}
+
+#[test]
+fn value_range_success() {
+    let mut graph = Graph::new();
+    assert_eq!(graph.value_range(), None);
+    for (name, value) in &[("Line 1", 7), ("Line 2", 3), ("Line 1", 12)] {
+        assert!(graph.add(name, *value).is_ok());
+    }
+    assert_eq!(graph.value_range(), Some((3, 12)));
+}
+
+#[test]
+fn print_n_times_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print_n_times(5).is_ok());
+    assert_eq!(graph.print_count(), 5);
+    assert!(graph.print_n_times(0).is_ok());
+    assert_eq!(graph.print_count(), 5);
+}
+
+#[test]
+fn print_hook_success() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let hook_counter = counter.clone();
+    let mut graph = detached(Graph::new().with_print_hook(move |graph| {
+        hook_counter.store(graph.print_count(), Ordering::SeqCst)
+    }));
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+    assert!(graph.print_n_times(2).is_ok());
+    assert_eq!(counter.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn buffer_success() {
+    let (mut graph, writer) = captured(Graph::new().with_fallback_size(80));
+    assert!(graph.add("Line", 0).is_ok());
+
+    graph.begin_buffer();
+    assert!(graph.print_n_times(3).is_ok());
+    assert_eq!(writer.lines(), 0);
+    assert!(graph.flush_buffer().is_ok());
+    assert_eq!(writer.lines(), 3);
+
+    assert!(graph.print().is_ok());
+    assert_eq!(writer.lines(), 4);
+}
+
+#[test]
+fn overflow_policy_success() {
+    let mut graph = Graph::with_prefix_length(20)
+        .with_fallback_size(40)
+        .with_overflow_policy(OverflowPolicy::Error)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    match graph.print().unwrap_err().code {
+        ErrorType::ColumnsOverflow { visible, total } => {
+            assert_eq!(visible, 7);
+            assert_eq!(total, 20);
+        }
+        code => panic!("unexpected error {:?}", code),
+    }
+    assert_eq!(graph.print_count(), 0);
+
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(20)
+            .with_fallback_size(40)
+            .with_overflow_policy(OverflowPolicy::Scroll),
+    );
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    for _ in 0..25 {
+        assert!(graph.print_raw().is_ok());
+    }
+    assert!(graph.remove(19).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add(20, 0).is_ok());
+    assert_eq!(graph.column_index(20), Some(19));
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 26);
+    for row in &rows {
+        assert_eq!(row.chars().count(), 40, "{}", row);
+    }
+    assert_eq!(rows[0], "                   6 → ┬┈┬┈┬┈┬┈┬┈┬┈┬┈…┈┈");
+    assert_eq!(rows[1], "                   7 → …╎┈╎┈╎┈╎┈╎┈╎┈┬┈…┈");
+    assert_eq!(rows[13], "                  19 → …╎┈╎┈╎┈╎┈╎┈╎┈┬┈┈┈");
+}
+
+#[test]
+fn scroll_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(20)
+            .with_fallback_size(40)
+            .with_overflow_policy(OverflowPolicy::Scroll),
+    );
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    graph.scroll_right(100);
+    graph.scroll_left(10);
+    assert!(graph.print_raw().is_ok());
+    graph.scroll_left(100);
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], "                  15 → …┬┈┬┈┬┈┬┈┬┈┬┈┬┈…┈");
+    assert_eq!(rows[1], "                   6 → ┬┈┬┈┬┈┬┈┬┈┬┈┬┈…┈┈");
+}
+
+#[test]
+fn visible_range_success() {
+    let mut graph = Graph::with_prefix_length(20)
+        .with_fallback_size(40)
+        .with_overflow_policy(OverflowPolicy::Scroll)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert_eq!(graph.visible_range(), (0, 0));
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    assert_eq!(graph.visible_range(), (0, 7));
+
+    graph.scroll_right(5);
+    assert_eq!(graph.visible_range(), (5, 12));
+    graph.scroll_right(100);
+    assert_eq!(graph.visible_range(), (19, 20));
+
+    // Nothing fits into a terminal without any width
+    for width in 0..3 {
+        let mut graph = Graph::with_prefix_length(0)
+            .with_fallback_size(width)
+            .with_output(OutputTarget::Custom(Box::new(io::sink())));
+        assert!(graph.add("Line", 0).is_ok());
+        assert_eq!(graph.visible_range(), (0, 0));
+        match graph.print().unwrap_err().code {
+            ErrorType::TerminalTooNarrow { .. } => {}
+            code => panic!("unexpected error {:?}", code),
+        }
+    }
+}
+
+#[test]
+fn column_gap_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_column_gap(2)
+            .with_fallback_size(80),
+    );
+    for i in 0..10 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.remove(5).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 3);
+    let columns =
+        |row: &str| row.chars().skip(7).step_by(4).take(10).collect::<String>();
+    assert_eq!(columns(&rows[0]), "┬┬┬┬┬┬┬┬┬┬");
+    assert_eq!(columns(&rows[1]), "╎╎╎╎╎┴╎╎╎╎");
+    assert!(rows[0].starts_with("   9 → ┬┈┈┈┬┈┈┈┬"));
+    assert!(rows[1].starts_with("   5 ← ╎┈┈┈╎┈┈┈╎"));
+
+    let mut graph = Graph::with_prefix_length(20)
+        .with_fallback_size(40)
+        .with_column_gap(4)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    for i in 0..10 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert_eq!(graph.visible_range(), (0, 3));
+}
+
+#[test]
+fn overflow_char_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(20)
+            .with_fallback_size(40)
+            .with_overflow_char('>')
+            .unwrap(),
+    );
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    assert!(graph.print_raw().is_ok());
+    let output = writer.output();
+    assert_eq!(output, "                   6 → ┬┈┬┈┬┈┬┈┬┈┬┈┬┈>┈┈\n");
+
+    match Graph::<u8>::new().with_overflow_char('\t') {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::InvalidCharacterWidth {
+                character: '\t',
+                width: 0
+            }
+        ),
+        Ok(_) => panic!("tab character accepted"),
+    }
+}
+
+#[test]
+fn line_separator_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_line_separator("|").with_fallback_size(80));
+    for i in 0..3 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    let output = writer.output();
+    assert_eq!(output.matches('|').count(), 2);
+}
+
+#[test]
+fn render_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_auto_prefix_length()
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line 1", 1).is_ok());
+    assert!(graph.add("Line 2", 2).is_ok());
+
+    let rows = graph.render().unwrap();
+    assert_eq!(rows, graph.render().unwrap());
+    assert_eq!(graph.print_count(), 0);
+
+    assert!(graph.print().is_ok());
+    let output = writer.output();
+    assert_eq!(output, format!("{}\n", rows[0]));
+    assert_ne!(rows, graph.render().unwrap());
+}
+
+#[test]
+fn line_age_success() {
+    let mut graph = Graph::with_prefix_length(20)
+        .with_fallback_size(30)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    for i in 0..20 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+
+    // The first print consumes the new data, every further one is idle
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.line_age(0), Some(0));
+    assert_eq!(graph.line_age(19), Some(0));
+    assert!(graph.print_n_times(3).is_ok());
+    assert_eq!(graph.line_age(0), Some(3));
+    assert_eq!(graph.line_age(19), Some(3));
+
+    // New data resets the age, also for lines outside of the terminal
+    assert!(graph.add(19, 1).is_ok());
+    assert_eq!(graph.line_age(19), Some(0));
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.line_age(0), Some(4));
+    assert_eq!(graph.line_age(19), Some(0));
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.line_age(0), Some(5));
+    assert_eq!(graph.line_age(19), Some(1));
+}
+
+#[test]
+fn line_is_stale_success() {
+    let mut graph = Graph::new()
+        .with_fallback_size(80)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.add("Line 2", 0).is_ok());
+    assert!(graph.print_n_times(5).is_ok());
+    assert!(graph.add("Line 2", 1).is_ok());
+    assert!(graph.print().is_ok());
+
+    assert!(graph.line_is_stale("Line 1", 4));
+    assert!(!graph.line_is_stale("Line 1", 5));
+    assert!(!graph.line_is_stale("Line 2", 0));
+    assert!(!graph.line_is_stale("Line 3", 0));
+}
+
+#[test]
+fn line_success() {
+    let mut line = Line::new("Line 1");
+    assert!(line.values().is_empty());
+    for i in 0..5 {
+        line.add_value(i);
+    }
+    assert_eq!(line.name(), "Line 1");
+    assert_eq!(line.values(), &[0, 1, 2, 3, 4]);
+    assert_eq!(line.clone(), line);
+}
+
+#[test]
+fn insert_line_success() {
+    let line = |name, values: &[u8]| {
+        let mut line = Line::new(name);
+        for value in values {
+            line.add_value(*value);
+        }
+        line
+    };
+
+    let mut graph = detached(Graph::new().with_max_columns(2));
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.insert_line(line("Line 2", &[1, 2])).is_ok());
+    assert_eq!(graph.line_values("Line 2"), Some(&[1, 2][..]));
+    assert_eq!(graph.all_lines(), vec!["Line 1", "Line 2"]);
+    assert!(graph.print().is_ok());
+
+    match graph.insert_line(line("Line 3", &[3])).unwrap_err().code {
+        ErrorType::MaxColumnsExceeded { limit, attempted } => {
+            assert_eq!(limit, 2);
+            assert_eq!(attempted, 3);
+        }
+        code => panic!("unexpected error {:?}", code),
+    }
+    match graph.insert_line(line("Line 1", &[3])).unwrap_err().code {
+        ErrorType::LineAlreadyExists { name } => assert_eq!(name, "Line 1"),
+        code => panic!("unexpected error {:?}", code),
+    }
+    assert_eq!(graph.line_values("Line 1"), Some(&[0][..]));
+
+    // Removed columns are reused unless the order is locked
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.insert_line(line("Line 3", &[3])).is_ok());
+    assert_eq!(graph.column_index("Line 3"), Some(0));
+
+    let mut graph = detached(Graph::new().with_lock_order());
+    assert!(graph.add("Line 1", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.insert_line(line("Line 2", &[1])).is_ok());
+    assert_eq!(graph.column_index("Line 2"), Some(1));
+}
+
+#[test]
+fn columns_iter_success() {
+    let mut graph = detached(Graph::new());
+    for (name, value) in &[("Line 1", 1), ("Line 2", 2), ("Line 1", 3)] {
+        assert!(graph.add(name, *value).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("Line 3", 4).is_ok());
+
+    let columns: Vec<_> = graph.columns_iter().collect();
+    assert_eq!(columns, vec![("Line 3", &[4][..]), ("Line 2", &[2][..])]);
+}
+
+#[test]
+fn columns_iter_mut_success() {
+    let mut graph = Graph::new();
+    for i in 0..10 {
+        assert!(graph.add("Line 1", i).is_ok());
+        assert!(graph.add("Line 2", i).is_ok());
+    }
+    for (name, mut values) in graph.columns_iter_mut() {
+        if name == "Line 1" {
+            values.retain(|v| v % 2 == 0);
+        }
+    }
+    let columns: Vec<_> = graph.columns_iter().collect();
+    assert_eq!(columns[0].1, &[0, 2, 4, 6, 8]);
+    assert_eq!(columns[1].1.len(), 10);
+}
+
+#[test]
+fn index_success() {
+    let mut graph = Graph::new();
+    for i in 0..3 {
+        assert!(graph.add("Line 1", i).is_ok());
+    }
+    assert_eq!(&graph["Line 1"], &[0, 1, 2]);
+    assert_eq!(graph.line_values("Line 1"), Some(&[0, 1, 2][..]));
+    assert_eq!(graph.line_values("Line 2"), None);
+}
+
+#[test]
+#[should_panic(expected = "Line 'Line 2' does not exist")]
+fn index_failure() {
+    let mut graph = Graph::new();
+    assert!(graph.add("Line 1", 0).is_ok());
+    let _ = &graph["Line 2"];
+}
+
+#[test]
+fn color_256_mode_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_256_color_mode().with_fallback_size(80));
+    for i in 0..10 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    let output = writer.output();
+    assert!(output.contains("\x1b[38;5;"));
+    assert!(!output.contains("\x1b[38;2;"));
+}
+
+#[test]
+fn color_8_mode_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_8_color_mode()
+            .with_group_color("Group", (255, 0, 0))
+            .with_fallback_size(80),
+    );
+    for i in 0..10 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert!(graph.add_to_group("Group", 0).is_ok());
+    assert!(graph.print().is_ok());
+    let output = writer.output();
+    for i in 0..8 {
+        assert!(output.contains(&format!("\x1b[38;5;{}m", i)));
+    }
+    assert!(!output.contains("\x1b[38;2;"));
+}
+
+#[test]
+fn print_raw_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_bold_latest().with_fallback_size(80));
+    for i in 0..5 {
+        assert!(graph.add(i, i).is_ok());
+    }
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.print().is_ok());
+
+    let rows = writer.rows();
+    assert!(!rows[0].contains('\x1b'));
+    assert!(rows[0].contains('┬'));
+    assert!(rows[1].contains('\x1b'));
+    assert_eq!(graph.print_count(), 2);
+}
+
+#[test]
+fn terminal_too_narrow_failure() {
+    let mut graph = Graph::with_prefix_length(200)
+        .with_fallback_size(80)
+        .with_output(OutputTarget::Custom(Box::new(io::sink())));
+    assert!(graph.add("Line", 0).is_ok());
+    match graph.print().unwrap_err().code {
+        ErrorType::TerminalTooNarrow { width, required } => {
+            assert_eq!(width, 80);
+            assert_eq!(required, 206);
+        }
+        code => panic!("unexpected error {:?}", code),
+    }
+    assert_eq!(graph.print_count(), 0);
+}
+
+#[test]
+fn duplicate_removal_failure() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line").is_ok());
+    assert_eq!(
+        graph.remove("Line").unwrap_err().code,
+        ErrorType::DuplicateRemoval {
+            name: "Line".to_owned()
+        }
+    );
+    assert!(graph.print().is_ok());
+    assert_eq!(
+        graph.remove("Line").unwrap_err().code,
+        ErrorType::LineDoesNotExist {
+            name: "Line".to_owned()
+        }
+    );
+}
+
+#[test]
+fn try_print_success() {
+    let mut graph = detached(Graph::new());
+    assert!(graph.add("Line", 0).is_ok());
+    graph.try_print();
+    assert_eq!(graph.print_count(), 1);
+
+    let mut graph = detached(Graph::with_prefix_length(200));
+    assert!(graph.add("Line", 0).is_ok());
+    graph.try_print();
+    assert_eq!(graph.print_count(), 0);
+}
+
+#[test]
+fn rain_graph_success() {
+    let mut graph = detached(rain_graph! {
+        prefix: 4,
+        lines: {
+            "Line 1" => 1,
+            "Line 2" => 2,
+        },
+    });
+    assert_eq!(graph.config().prefix_len, 7);
+    assert_eq!(graph.active_lines(), vec!["Line 1", "Line 2"]);
+    assert_eq!(graph.value_range(), Some((1, 2)));
+    assert!(graph.print().is_ok());
+
+    let graph: Graph<u8> = rain_graph! { lines: {} };
+    assert!(graph.is_empty());
+}
+
+#[test]
+fn label_alignment_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(6)
+            .with_label_alignment(LabelAlignment::Center)
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("ab", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("cd", 0).is_ok());
+    assert!(graph.set_line_alignment("cd", LabelAlignment::Left).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph
+        .set_line_alignment("ef", LabelAlignment::Left)
+        .is_err());
+
+    let rows = writer.rows();
+    assert!(rows[0].starts_with("  ab   → "));
+    assert!(rows[1].starts_with("cd     → "));
+}
+
+#[test]
+fn separator_char_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_separator_char('·')
+            .unwrap()
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print().is_ok());
+    let output = writer.output();
+    assert!(output.contains('·'));
+    assert!(!output.contains('┈'));
+
+    assert!(Graph::<u8>::new().with_separator_char(' ').is_ok());
+    assert!(Graph::<u8>::new().with_separator_char('\u{200b}').is_err());
+}
+
+#[test]
+fn markers_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_start_marker(" > ")
+            .unwrap()
+            .with_end_marker("<<<")
+            .unwrap()
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line").is_ok());
+    assert!(graph.print().is_ok());
+
+    let rows = writer.rows();
+    assert!(rows[0].starts_with("Line > "));
+    assert!(rows[1].starts_with("Line<<<"));
+
+    match Graph::<u8>::new().with_start_marker("⟶") {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::InvalidMarkerWidth {
+                marker: "⟶".to_owned(),
+                width: 1
+            }
+        ),
+        Ok(_) => panic!("marker accepted"),
+    }
+}
+
+#[test]
+fn title_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_title("Dashboard").with_fallback_size(80));
+    assert!(graph.add("Line", 0).is_ok());
+    assert_eq!(graph.render().unwrap().len(), 2);
+    assert!(graph.print_raw().is_ok());
+    assert_eq!(graph.render().unwrap().len(), 1);
+    assert!(graph.print().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with("┈┈┈"));
+    assert!(rows[0].contains(" Dashboard "));
+    assert!(rows[0].ends_with("┈┈┈"));
+}
+
+#[test]
+fn column_labels_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_column_labels()
+            .with_fallback_size(80),
+    );
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print_n_times(2).is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0], "       CPRAIO");
+}
+
+#[test]
+fn row_numbers_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_row_numbers()
+            .with_timestamp_format("%Y")
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print_n_times(2).is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(&rows[0][4..12], " [0000] ");
+    assert_eq!(&rows[1][4..12], " [0001] ");
+}
+
+#[test]
+fn value_display_format_success() {
+    let graph: Graph<u32> = Graph::new();
+    assert_eq!(graph.format_value(1024), "1024");
+
+    for (format, expected) in &[
+        ("{}", "42"),
+        ("{:.1}", "42.0"),
+        ("{:<5}|", "42   |"),
+        ("{:^6}", "  42  "),
+        ("[{:>5.1}] MB", "[ 42.0] MB"),
+        ("{:06.2}", "042.00"),
+        ("{:<05}", "00042"),
+        ("{:+}", "+42"),
+        ("{:+06.1}", "+042.0"),
+        ("{:^+7}", "  +42  "),
+    ] {
+        let graph: Graph<u32> =
+            Graph::new().with_value_display_format(format).unwrap();
+        assert_eq!(graph.format_value(42), *expected);
+    }
+
+    // The flags behave like the ones of the standard library
+    for (format, value, expected) in &[
+        ("{:06.2}", -5, "-05.00"),
+        ("{:+06.2}", -5, "-05.00"),
+        ("{:+}", 0, "+0"),
+        ("{:03}", -1234, "-1234"),
+    ] {
+        let graph: Graph<i32> =
+            Graph::new().with_value_display_format(format).unwrap();
+        assert_eq!(graph.format_value(*value), *expected);
+    }
+
+    for format in &[
+        "", "%.2f", "{:x}", "{:.}", "{} {}", "{", "{:#6}", "{:-6}", "{:*>6}",
+        "{:0+6}", "{:6e}",
+    ] {
+        match Graph::<u32>::new().with_value_display_format(format) {
+            Err(e) => assert_eq!(
+                e.code,
+                ErrorType::InvalidFormat {
+                    format: format.to_string()
+                }
+            ),
+            Ok(_) => panic!("format '{}' accepted", format),
+        }
+    }
+}
+
+#[test]
+fn compact_mode_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_compact_mode()
+            .with_fallback_size(80),
+    );
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add("CPU", 1).is_ok());
+    assert!(graph.print_raw().is_ok());
+
+    let rows = writer.rows();
+    let columns = |row: &str| row.chars().skip(7).take(4).collect::<String>();
+    assert_eq!(columns(&rows[0]), "┬┬┬┈");
+    assert_eq!(columns(&rows[1]), "│╎╎┈");
+}
+
+#[test]
+fn print_summary_success() {
+    let (mut graph, writer) = captured(Graph::with_prefix_length(4));
+    assert!(graph.print_summary().is_ok());
+    for (name, value) in &[("CPU", 1), ("RAM", 4), ("IO", 2), ("NET", 9)] {
+        assert!(graph.add(name, *value).is_ok());
+    }
+    assert!(graph.remove("NET").is_ok());
+    assert!(graph.print_summary().is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows[0], "       min=- max=- mean=- active=0");
+    assert_eq!(
+        rows[1],
+        "       min=1 max=4 mean=2.3333333333333335 active=3"
+    );
+}
+
+#[test]
+fn print_on_remove_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_print_on_remove(true)
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line").is_ok());
+    assert!(graph.all_lines().is_empty());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[1].contains(" ← "));
+}
+
+#[test]
+fn print_on_add_success() {
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_print_on_add(true)
+            .with_print_on_remove(true)
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Line", 0).is_ok());
+    assert!(graph.add("Line", 1).is_ok());
+    assert!(graph.remove("Line").is_ok());
+
+    let rows = writer.rows();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].contains(" → "));
+    assert!(rows[1].contains(" ← "));
+}
+
+#[test]
+fn line_value_count_success() {
+    let mut graph = Graph::new();
+    for i in 0..10 {
+        assert!(graph.add("Line 1", i).is_ok());
+    }
+    assert!(graph.add("Line 2", 0).is_ok());
+
+    assert_eq!(graph.line_value_count("Line 1"), Some(10));
+    assert_eq!(graph.line_value_count("Line 2"), Some(1));
+    assert_eq!(graph.line_value_count("Line 3"), None);
+}
+
+#[test]
+fn total_value_count_success() {
+    let mut graph = detached(Graph::new());
+    assert_eq!(graph.total_value_count(), 0);
+    for i in 0..10 {
+        assert!(graph.add("Line 1", i).is_ok());
+        assert!(graph.add("Line 2", i).is_ok());
+    }
+    assert_eq!(graph.total_value_count(), 20);
+
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("Line 1").is_ok());
+    assert_eq!(graph.total_value_count(), 10);
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.total_value_count(), 10);
+}
+
+#[test]
+fn peak_line_count_success() {
+    let mut graph = detached(Graph::new());
+    assert_eq!(graph.peak_line_count(), 0);
+    for i in 0..3 {
+        assert!(graph.add(i, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove(0).is_ok());
+    assert!(graph.remove(1).is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.all_lines().len(), 1);
+    assert_eq!(graph.peak_line_count(), 3);
+
+    assert!(graph.add(3, 0).is_ok());
+    assert_eq!(graph.peak_line_count(), 3);
+}
+
+#[test]
+fn auto_scale_delay_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_auto_scale_delay(2).with_fallback_size(80));
+    assert!(graph.add("Low", 0).is_ok());
+    assert!(graph.add("High", 100).is_ok());
+    assert!(graph.print_n_times(3).is_ok());
+
+    let rows = writer.rows();
+    let red = "\x1b[38;2;255;125;125m";
+    assert!(!rows[0].contains(red));
+    assert!(!rows[1].contains(red));
+    assert!(rows[2].contains(red));
+
+    // The blocks of the sparkline mode keep their scale, too
+    let (mut graph, writer) = captured(
+        Graph::with_prefix_length(4)
+            .with_sparkline_mode()
+            .with_auto_scale_delay(2)
+            .with_fallback_size(20),
+    );
+    assert!(graph.add("Low", 0).is_ok());
+    assert!(graph.add("High", 100).is_ok());
+    assert!(graph.print_raw().is_ok());
+    assert!(graph.add("Low", 50).is_ok());
+    assert!(graph.print_n_times(2).is_ok());
+
+    let blocks: Vec<String> = writer
+        .rows()
+        .iter()
+        .map(|row| strip_ansi(row).chars().skip(7).step_by(2).take(2).collect())
+        .collect();
+    assert_eq!(blocks, vec!["▁▁", "▁▁", "▅█"]);
+}
+
+#[test]
+fn per_line_scale_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_per_line_scale().with_fallback_size(80));
+    for (name, value) in &[("Wide", 0), ("Wide", 200), ("Narrow", 99)] {
+        assert!(graph.add(name, *value).is_ok());
+    }
+    assert!(graph.add("Narrow", 101).is_ok());
+    assert!(graph.print().is_ok());
+
+    let output = writer.output();
+    assert_eq!(output.matches("\x1b[38;2;255;125;125m").count(), 2);
+}
+
+#[test]
+fn swap_lines_success() {
+    let mut graph = Graph::new();
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.add("CPU", 1).is_ok());
+    assert!(graph.swap_lines("CPU", "IO").is_ok());
+    assert_eq!(graph.all_lines(), vec!["IO", "RAM", "CPU"]);
+    assert_eq!(graph.line_values("CPU"), Some(&[0, 1][..]));
+
+    match graph.swap_lines("CPU", "NET") {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::LineDoesNotExist {
+                name: "NET".to_owned()
+            }
+        ),
+        Ok(_) => panic!("missing line swapped"),
+    }
+}
+
+#[test]
+fn move_lines_success() {
+    let mut graph = Graph::new();
+    for name in &["CPU", "RAM", "IO", "NET"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.move_to_front("IO").is_ok());
+    assert_eq!(graph.all_lines(), vec!["IO", "CPU", "RAM", "NET"]);
+    assert!(graph.move_to_back("CPU").is_ok());
+    assert_eq!(graph.all_lines(), vec!["IO", "RAM", "NET", "CPU"]);
+    assert_eq!(graph.column_index("CPU"), Some(3));
+
+    assert!(graph.move_to_front("DISK").is_err());
+    assert!(graph.move_to_back("DISK").is_err());
+}
+
+#[test]
+fn lock_order_success() {
+    let mut graph = detached(Graph::new().with_lock_order());
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("RAM").is_ok());
+    assert!(graph.print().is_ok());
+    assert!(graph.add("NET", 0).is_ok());
+    assert_eq!(graph.all_lines(), vec!["CPU", "IO", "NET"]);
+    assert_eq!(graph.column_index("NET"), Some(3));
+
+    assert!(graph.move_to_front("IO").is_ok());
+    assert_eq!(graph.all_lines(), vec!["IO", "CPU", "NET"]);
+    assert_eq!(graph.column_index("NET"), Some(4));
+}
+
+#[test]
+fn compact_removed_success() {
+    let mut graph = detached(Graph::new().with_compact_removed());
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("RAM").is_ok());
+    assert!(graph.print().is_ok());
+    assert_eq!(graph.column_index("IO"), Some(1));
+
+    assert!(graph.add("NET", 0).is_ok());
+    assert_eq!(graph.all_lines(), vec!["CPU", "IO", "NET"]);
+}
+
+#[test]
+fn sort_lines_by_key_success() {
+    let mut graph = detached(Graph::new());
+    for (name, value) in &[("CPU", 3), ("NET", 0), ("RAM", 1), ("IO", 2)] {
+        assert!(graph.add(name, *value).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    assert!(graph.remove("NET").is_ok());
+    assert!(graph.print_n_times(2).is_ok());
+
+    graph.sort_lines_by_key(|name, _| name.to_owned());
+    assert_eq!(graph.all_lines(), vec!["CPU", "IO", "RAM"]);
+    assert_eq!(graph.column_index("RAM"), Some(2));
+    graph.sort_lines_by_key(|_, values| values.last().cloned());
+    assert_eq!(graph.all_lines(), vec!["RAM", "IO", "CPU"]);
+    assert_eq!(graph.column_index("CPU"), Some(2));
+}
+
+#[test]
+fn load_from_csv_success() {
+    let mut graph = Graph::new();
+    for i in 0..3 {
+        assert!(graph.add("CPU, total", i).is_ok());
+    }
+    assert!(graph.add("\"RAM\"", 7).is_ok());
+    let path = std::env::temp_dir().join("rain_load_from_csv.csv");
+    let mut file = std::fs::File::create(&path).unwrap();
+    assert!(graph.export_csv(&mut file).is_ok());
+
+    let loaded: Graph<u32> = Graph::with_load_from_csv(&path).unwrap();
+    assert_eq!(loaded.all_lines(), vec!["CPU, total", "\"RAM\""]);
+    assert_eq!(loaded.line_values("CPU, total"), Some(&[0, 1, 2][..]));
+    assert_eq!(loaded.line_values("\"RAM\""), Some(&[7][..]));
+
+    std::fs::write(&path, "tick,CPU\n0,1\n1,x\n").unwrap();
+    match Graph::<u32>::with_load_from_csv(&path) {
+        Err(e) => {
+            assert_eq!(e.code, ErrorType::Other);
+            assert!(std::error::Error::source(&e).is_some());
+        }
+        Ok(_) => panic!("malformed value loaded"),
+    }
+    std::fs::write(&path, "tick,CPU\n0,1,2\n").unwrap();
+    assert!(Graph::<u32>::with_load_from_csv(&path).is_err());
+}
+
+#[test]
+fn export_csv_with_timestamps_success() {
+    let mut graph = Graph::new();
+    assert!(graph.add("CPU", 1).is_ok());
+    graph = graph.with_timestamp_prefix();
+    assert!(graph.add("RAM", 3).is_ok());
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(graph.add("CPU", 2).is_ok());
+    assert!(graph.add("RAM", 4).is_ok());
+
+    let mut csv = vec![];
+    assert!(graph.export_csv_with_timestamps(&mut csv).is_ok());
+    let csv = String::from_utf8(csv).unwrap();
+    let rows: Vec<_> = csv.lines().collect();
+    assert_eq!(rows[0], "tick,elapsed_ms,CPU,RAM");
+    assert_eq!(rows[1], "0,0,1,3");
+    assert!(rows[2].starts_with("1,"));
+    assert!(rows[2].ends_with(",2,4"));
+    let elapsed: u128 = rows[2].split(',').nth(1).unwrap().parse().unwrap();
+    assert!(elapsed >= 20);
+}
+
+#[test]
+fn time_series_success() {
+    let mut graph = Graph::new();
+    assert!(graph.add("CPU", 1).is_ok());
+    graph = graph.with_time_series();
+    assert!(graph.add("CPU", 2).is_ok());
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(graph.add("CPU", 3).is_ok());
+
+    let window = |graph: &Graph<u32>, ms| {
+        graph
+            .time_windowed_values("CPU", Duration::from_millis(ms))
+            .map(|v| v.to_vec())
+    };
+    assert_eq!(window(&graph, 25), Some(vec![3]));
+    assert_eq!(window(&graph, 10_000), Some(vec![2, 3]));
+    assert_eq!(
+        graph.time_windowed_values("RAM", Duration::from_secs(1)),
+        None
+    );
+}
+
+#[test]
+fn time_window_success() {
+    let (mut graph, writer) = captured(
+        Graph::new()
+            .with_time_window(Duration::from_millis(25))
+            .with_fallback_size(80),
+    );
+    assert!(graph.add("Low", 1000).is_ok());
+    assert!(graph.add("High", 0).is_ok());
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(graph.add("Low", 0).is_ok());
+    assert!(graph.add("High", 100).is_ok());
+    assert!(graph.print().is_ok());
+
+    let output = writer.output();
+    assert_eq!(output.matches("\x1b[38;2;255;125;125m").count(), 1);
+    assert_eq!(graph.line_values("Low"), Some(&[1000, 0][..]));
+}
+
+#[test]
+fn diff_snapshots_success() {
+    let mut graph = detached(Graph::new());
+    for name in &["CPU", "RAM", "IO"] {
+        assert!(graph.add(name, 0).is_ok());
+    }
+    assert!(graph.print().is_ok());
+    let a = graph.snapshot();
+
+    assert!(graph.add("CPU", 1).is_ok());
+    assert!(graph.add("CPU", 2).is_ok());
+    assert!(graph.add("NET", 3).is_ok());
+    assert!(graph.remove("IO").is_ok());
+    let b = graph.snapshot();
+    assert_eq!(b.values("IO"), None);
+
+    let diff = Graph::diff_snapshots(&a, &b);
+    assert_eq!(diff.added_lines, vec!["NET"]);
+    assert_eq!(diff.removed_lines, vec!["IO"]);
+    assert_eq!(diff.changed_lines, vec![("CPU".to_owned(), vec![1, 2])]);
+    assert_eq!(Graph::diff_snapshots(&b, &b), Default::default());
+}
+
+#[test]
+fn graph_eq_success() {
+    let mut a = detached(Graph::new());
+    let mut b = detached(Graph::new().with_bold_latest());
+    assert!(a == b);
+
+    for graph in &mut [&mut a, &mut b] {
+        assert!(graph.add("CPU", 1).is_ok());
+        assert!(graph.add("RAM", 2).is_ok());
+    }
+    assert!(a.print().is_ok());
+    assert!(a == b);
+
+    assert!(b.remove("RAM").is_ok());
+    assert!(a != b);
+    assert!(a.remove("RAM").is_ok());
+    assert!(a == b);
+
+    assert!(a.add("CPU", 3).is_ok());
+    assert!(a != b);
+    assert!(Graph::<u32>::with_prefix_length(4) != Graph::new());
+}
+
+#[test]
+fn replay_success() {
+    let mut recorded = Graph::new();
+    for i in 0..5 {
+        assert!(recorded.add("CPU", i).is_ok());
+    }
+    assert!(recorded.add("RAM", 7).is_ok());
+    assert!(recorded.add("RAM", 8).is_ok());
+
+    let mut graph = detached(Graph::new());
+    let mut replay = Replay::new(recorded.snapshot());
+    assert!(replay.step(&mut graph));
+    assert_eq!(graph.line_values("CPU"), Some(&[0][..]));
+    assert_eq!(graph.line_values("RAM"), Some(&[7][..]));
+
+    let mut steps = 1;
+    while replay.step(&mut graph) {
+        assert!(graph.print().is_ok());
+        steps += 1;
+    }
+    assert_eq!(steps, 5);
+    assert!(!replay.step(&mut graph));
+    assert!(graph == recorded);
+}
+
+#[test]
+fn merge_success() {
+    let mut first = Graph::with_prefix_length(4);
+    for name in &["CPU", "RAM"] {
+        assert!(first.add(name, 1).is_ok());
+    }
+
+    let mut second = detached(Graph::new().with_time_series());
+    for name in &["IO", "CPU", "NET"] {
+        assert!(second.add(name, 2).is_ok());
+    }
+    assert!(second.add("CPU", 3).is_ok());
+    assert!(second.print().is_ok());
+    assert!(second.remove("NET").is_ok());
+
+    let merged = first.merge(second).unwrap();
+    assert_eq!(merged.config().prefix_len, 7);
+    assert_eq!(merged.all_lines(), vec!["CPU", "RAM", "IO"]);
+    assert_eq!(merged.line_values("CPU"), Some(&[1, 2, 3][..]));
+    let window = Duration::from_secs(60);
+    assert_eq!(
+        merged.time_windowed_values("CPU", window),
+        Some(&[2, 3][..])
+    );
+    assert_eq!(merged.time_windowed_values("IO", window), Some(&[2][..]));
+}
+
+#[test]
+fn merge_max_columns_failure() {
+    let graph = |names: &[&str]| {
+        let mut graph = Graph::new();
+        for name in names {
+            assert!(graph.add(name, 1).is_ok());
+        }
+        graph
+    };
+
+    // Lines which exist in both graphs do not need another column
+    let first = graph(&["x"]).with_max_columns(2);
+    let merged = first.merge(graph(&["x", "y"])).unwrap();
+    assert_eq!(merged.all_lines(), vec!["x", "y"]);
+
+    let first = graph(&["x"]).with_max_columns(1);
+    match first.merge(graph(&["y", "z"])) {
+        Err(e) => assert_eq!(
+            e.code,
+            ErrorType::MaxColumnsExceeded {
+                limit: 1,
+                attempted: 3
+            }
+        ),
+        Ok(graph) => panic!("merged into {:?}", graph.all_lines()),
+    }
+}
+
+#[test]
+fn time_window_truncated_values_success() {
+    let mut graph = Graph::new()
+        .with_fallback_size(80)
+        .with_timestamp_prefix()
+        .with_time_window(Duration::from_secs(60));
+    for i in 0..5 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+    for (_, mut values) in graph.columns_iter_mut() {
+        values.truncate(2);
+    }
+
+    assert!(graph.render().is_ok());
+    assert_eq!(
+        graph.time_windowed_values("CPU", Duration::from_secs(60)),
+        Some(&[0, 1][..])
+    );
+    let mut csv = vec![];
+    assert!(graph.export_csv_with_timestamps(&mut csv).is_ok());
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 3);
+    assert!(csv.ends_with(",1\n"));
+}
+
+#[test]
+fn columns_iter_mut_truncate_success() {
+    let mut graph = Graph::new();
+    for i in 0..2 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+    graph = graph.with_timestamp_prefix();
+    for i in 2..5 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+    let csv = |graph: &Graph<u32>| {
+        let mut csv = vec![];
+        assert!(graph.export_csv_with_timestamps(&mut csv).is_ok());
+        String::from_utf8(csv).unwrap()
+    };
+
+    for (_, mut values) in graph.columns_iter_mut() {
+        values.truncate(3);
+    }
+    assert_eq!(csv(&graph), "tick,elapsed_ms,CPU\n0,,0\n1,,1\n2,0,2\n");
+
+    for (_, mut values) in graph.columns_iter_mut() {
+        values.retain(|v| v % 2 == 0);
+    }
+    assert_eq!(csv(&graph), "tick,elapsed_ms,CPU\n0,,0\n1,0,2\n");
+
+    for (_, mut values) in graph.columns_iter_mut() {
+        values[0] = 10;
+        values.clear();
+    }
+    assert_eq!(csv(&graph), "tick,elapsed_ms,CPU\n");
+}
+
+#[test]
+fn columns_iter_mut_resample_success() {
+    let mut graph = Graph::new();
+    for i in 0..4 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+    graph = graph.with_time_series();
+    for i in 4..6 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+
+    // Replace every pair of values by its maximum
+    for (_, mut values) in graph.columns_iter_mut() {
+        let resampled: Vec<_> = values
+            .chunks(2)
+            .map(|c| c.iter().max().cloned().unwrap())
+            .collect();
+        values.clear();
+        values.extend(resampled);
+        values.push(10);
+    }
+    assert_eq!(&graph["CPU"], &[1, 3, 5, 10]);
+
+    // All values got a time while the time series is enabled
+    let mut csv = vec![];
+    assert!(graph.export_csv_with_timestamps(&mut csv).is_ok());
+    let csv = String::from_utf8(csv).unwrap();
+    let rows: Vec<_> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 4);
+    for row in rows {
+        assert!(!row.split(',').nth(1).unwrap().is_empty(), "{}", row);
+    }
+
+    let mut graph = Graph::new();
+    assert!(graph.add("CPU", 1).is_ok());
+    for (_, mut values) in graph.columns_iter_mut() {
+        values.push(2);
+    }
+    assert_eq!(&graph["CPU"], &[1, 2]);
+}
+
+#[test]
+fn column_separators_fit_terminal_success() {
+    let configs: [fn(Graph<u32>) -> Graph<u32>; 6] = [
+        |g| g.with_column_gap(5),
+        |g| g.with_column_gap(8),
+        |g| g.with_column_gap(2).with_line_separator(" | "),
+        |g| g.with_line_separator("-----"),
+        |g| g.with_group_separator(1).with_column_gap(1),
+        |g| {
+            g.with_group("A", &["0", "1", "2"])
+                .with_group("B", &["3", "4"])
+                .with_column_gap(3)
+        },
+    ];
+    for config in configs {
+        let (mut graph, writer) = captured(config(
+            Graph::new()
+                .with_fallback_size(80)
+                .with_overflow_policy(OverflowPolicy::Scroll),
+        ));
+        for i in 0..40 {
+            assert!(graph.add(i, i).is_ok());
+        }
+        assert!(graph.print_raw().is_ok());
+        assert!(graph.print_raw().is_ok());
+
+        for row in writer.rows() {
+            assert_eq!(row.chars().count(), 80, "{}", row);
+        }
+    }
+}
+
+#[test]
+fn color_8_mode_single_value_success() {
+    let (mut graph, writer) =
+        captured(Graph::new().with_8_color_mode().with_fallback_size(80));
+    assert!(graph.add("Line 1", 5).is_ok());
+    assert!(graph.add("Line 2", 5).is_ok());
+    assert!(graph.print().is_ok());
+
+    let output = writer.output();
+    assert_eq!(output.matches("\x1b[38;5;4m").count(), 2);
+    assert!(!output.contains("\x1b[38;5;0m"));
+}
+
+#[test]
+fn load_from_csv_with_timestamps_success() {
+    let mut graph = Graph::new().with_timestamp_prefix();
+    for i in 0..3 {
+        assert!(graph.add("CPU", i).is_ok());
+    }
+    assert!(graph.add("RAM", 7).is_ok());
+    let path = std::env::temp_dir().join("rain_load_with_timestamps.csv");
+    let mut file = std::fs::File::create(&path).unwrap();
+    assert!(graph.export_csv_with_timestamps(&mut file).is_ok());
+
+    let loaded: Graph<u32> = Graph::with_load_from_csv(&path).unwrap();
+    assert_eq!(loaded.all_lines(), vec!["CPU", "RAM"]);
+    assert!(loaded.columns_iter().eq(graph.columns_iter()));
+
+    for content in &[
+        "tick,CPU,CPU\n0,1,2\n",
+        "tick,CPU,\n0,1,2\n",
+        "time,CPU\n0,1\n",
+        "",
+    ] {
+        std::fs::write(&path, content).unwrap();
+        match Graph::<u32>::with_load_from_csv(&path) {
+            Err(e) => assert_eq!(e.code, ErrorType::Other),
+            Ok(_) => panic!("invalid header '{}' accepted", content),
+        }
+    }
+}